@@ -0,0 +1,176 @@
+//! Formatting benchmarks.
+//!
+//! Run with `cargo bench --features bench`. An optional argument filters
+//! the benchmarks by name, e.g. `cargo bench --features bench -- iso8601`.
+//!
+//! This harness has no external dependencies: it times a fixed number of
+//! iterations per benchmark and counts heap allocations through a wrapping
+//! global allocator, so results can be collected offline.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Write,
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use time_format::{DateFormat, TimeStampMs};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Minimum wall-clock time spent measuring each benchmark.
+const TARGET_TIME: Duration = Duration::from_millis(500);
+
+/// A fixed timestamp, so that every run formats the same date.
+const TS: i64 = 1_747_751_445; // 2025-05-20T14:30:45Z
+
+struct Bencher {
+    filter: Option<String>,
+}
+
+impl Bencher {
+    fn run<T>(&self, name: &str, mut f: impl FnMut() -> T) {
+        if let Some(filter) = &self.filter {
+            if !name.contains(filter.as_str()) {
+                return;
+            }
+        }
+
+        // Warm up, and estimate how many iterations fit in the target time
+        let mut iters: u64 = 1;
+        loop {
+            let start = Instant::now();
+            for _ in 0..iters {
+                black_box(f());
+            }
+            if start.elapsed() >= TARGET_TIME / 10 {
+                break;
+            }
+            iters *= 2;
+        }
+        iters *= 10;
+
+        let allocs_before = ALLOCATIONS.load(Ordering::Relaxed);
+        let bytes_before = ALLOCATED_BYTES.load(Ordering::Relaxed);
+        let start = Instant::now();
+        for _ in 0..iters {
+            black_box(f());
+        }
+        let elapsed = start.elapsed();
+        let allocs = ALLOCATIONS.load(Ordering::Relaxed) - allocs_before;
+        let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes_before;
+
+        println!(
+            "{:<40} {:>10.1} ns/iter {:>8.2} allocs/iter {:>8.1} bytes/iter",
+            name,
+            elapsed.as_nanos() as f64 / iters as f64,
+            allocs as f64 / iters as f64,
+            bytes as f64 / iters as f64,
+        );
+    }
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`; anything else is a name filter
+    let filter = std::env::args().skip(1).find(|arg| !arg.starts_with('-'));
+    let b = Bencher { filter };
+    let ts_ms = TimeStampMs::new(TS, 123);
+
+    b.run("components_utc", || {
+        time_format::components_utc(black_box(TS))
+    });
+    b.run("components_local", || {
+        time_format::components_local(black_box(TS))
+    });
+
+    b.run("strftime_utc", || {
+        time_format::strftime_utc("%Y-%m-%d %H:%M:%S", black_box(TS))
+    });
+    b.run("strftime_local", || {
+        time_format::strftime_local("%Y-%m-%d %H:%M:%S", black_box(TS))
+    });
+    b.run("strftime_ms_utc", || {
+        time_format::strftime_ms_utc("%Y-%m-%d %H:%M:%S.{ms}", black_box(ts_ms))
+    });
+    b.run("strftime_ms_local", || {
+        time_format::strftime_ms_local("%Y-%m-%d %H:%M:%S.{ms}", black_box(ts_ms))
+    });
+
+    b.run("format_iso8601_utc", || {
+        time_format::format_iso8601_utc(black_box(TS))
+    });
+    b.run("format_iso8601_ms_utc", || {
+        time_format::format_iso8601_ms_utc(black_box(ts_ms))
+    });
+    b.run("format_iso8601_local", || {
+        time_format::format_iso8601_local(black_box(TS))
+    });
+    b.run("format_iso8601_ms_local", || {
+        time_format::format_iso8601_ms_local(black_box(ts_ms))
+    });
+
+    for (name, format) in [
+        ("RFC3339", DateFormat::RFC3339),
+        ("RFC2822", DateFormat::RFC2822),
+        ("HTTP", DateFormat::HTTP),
+        ("SQL", DateFormat::SQL),
+        ("LongDate", DateFormat::LongDate),
+    ] {
+        b.run(&format!("format_common_utc/{}", name), || {
+            time_format::format_common_utc(black_box(TS), format)
+        });
+        b.run(&format!("format_common_ms_utc/{}", name), || {
+            time_format::format_common_ms_utc(black_box(ts_ms), format)
+        });
+        b.run(&format!("format_common_local/{}", name), || {
+            time_format::format_common_local(black_box(TS), format)
+        });
+    }
+
+    // Display adapters, writing into a reused buffer
+    let mut buf = String::with_capacity(64);
+    b.run("display_utc", || {
+        buf.clear();
+        write!(
+            buf,
+            "{}",
+            black_box(ts_ms).display_utc("%Y-%m-%d %H:%M:%S.{ms}")
+        )
+        .unwrap();
+        buf.len()
+    });
+    b.run("display_iso8601", || {
+        buf.clear();
+        write!(buf, "{}", black_box(ts_ms).iso8601()).unwrap();
+        buf.len()
+    });
+
+    b.run("validate_format", || {
+        time_format::validate_format(black_box("%a, %d %b %Y %H:%M:%S.{ms} %z"))
+    });
+}
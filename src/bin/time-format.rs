@@ -0,0 +1,339 @@
+//! Convert and format timestamps from the command line.
+//!
+//! Build with `cargo install time-format --features cli`.
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Component, Path, PathBuf},
+    process,
+};
+
+use time_format::{
+    rewrite::Rewriter, CustomFormat, DateFormat, EpochDetector, EpochUnit, Error, FormatRef,
+    TimeStampMs, TimeZone,
+};
+
+const USAGE: &str = "\
+Usage: time-format [OPTIONS] [TIMESTAMP]...
+       time-format --rewrite [OPTIONS] [FILE]...
+
+Convert timestamps and print them in the requested format.
+Timestamps are read from standard input, one per line, if none are given.
+
+With --rewrite, copy the files (or standard input) to standard output,
+replacing the epoch numbers, ISO 8601 dates and syslog dates they contain.
+
+Accepted timestamps:
+  1747751445               integer number of seconds, milliseconds,
+                           microseconds or nanoseconds (see --unit)
+  1747751445.123           seconds with a fractional part
+  2025-05-20T14:30:45Z     RFC 3339
+  Tue, 20 May 2025 14:30:45 GMT
+                           HTTP date
+
+Options:
+  -f, --format FORMAT  Output format: RFC3339 (default), RFC2822, HTTP, SQL,
+                       US, European, ShortDate, LongDate, ShortTime,
+                       LongTime, DateTime, RFC3164, RFC5424, CommonLog,
+                       ISO8601Basic, ISO8601BasicOffset, or a strftime
+                       pattern containing %
+  -z, --zone ZONE      Output time zone: UTC (default), local, an offset such
+                       as +05:30, a zone name such as Europe/Paris, or a
+                       POSIX TZ rule such as EST5EDT
+  -u, --unit UNIT      Unit of integer timestamps: auto (default), s, ms, us,
+                       ns; auto detects it for dates from 2000 to 2100, and
+                       reads other values as seconds
+  -r, --rewrite        Rewrite the timestamps found in log files
+      --input-zone ZONE
+                       With --rewrite, time zone of the dates without a UTC
+                       offset: UTC (default), local, or an offset
+  -h, --help           Print this help
+  -V, --version        Print the version
+";
+
+enum Output {
+    Common(DateFormat),
+    Strftime(CustomFormat),
+}
+
+impl Output {
+    fn format_ref(&self) -> FormatRef<'_> {
+        match self {
+            Output::Common(format) => FormatRef::Date(*format),
+            Output::Strftime(format) => format.format_ref(),
+        }
+    }
+}
+
+struct Options {
+    output: Output,
+    zone: TimeZone,
+    /// `None` to detect the unit of each timestamp
+    unit: Option<EpochUnit>,
+    rewrite: bool,
+    input_zone: TimeZone,
+    inputs: Vec<String>,
+}
+
+fn parse_zone(zone: &str) -> Result<TimeZone, String> {
+    if zone.eq_ignore_ascii_case("utc") || zone.eq_ignore_ascii_case("z") {
+        return Ok(TimeZone::Utc);
+    }
+    if zone.eq_ignore_ascii_case("local") {
+        return Ok(TimeZone::Local);
+    }
+    if zone.starts_with(['+', '-']) {
+        let bad_offset = || format!("invalid UTC offset: {}", zone);
+        let sign = if zone.starts_with('-') { -1 } else { 1 };
+        let digits: String = zone[1..].chars().filter(|&c| c != ':').collect();
+        if !matches!(digits.len(), 2 | 4) || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(bad_offset());
+        }
+        let hours: i32 = digits[..2].parse().map_err(|_| bad_offset())?;
+        let minutes: i32 = digits[2..].parse().unwrap_or(0);
+        if hours > 23 || minutes > 59 {
+            return Err(bad_offset());
+        }
+        return Ok(TimeZone::FixedOffset(sign * (hours * 3600 + minutes * 60)));
+    }
+    // Named zones are resolved by the C library, through the TZ variable,
+    // which silently falls back to UTC for the names it doesn't know
+    if !zone_file_exists(zone) && !is_posix_zone(zone) {
+        return Err(format!("unknown time zone: {}", zone));
+    }
+    env::set_var("TZ", zone);
+    Ok(TimeZone::Local)
+}
+
+/// Whether a zone name such as `Europe/Paris` is in the time zone database.
+fn zone_file_exists(zone: &str) -> bool {
+    let name = zone.strip_prefix(':').unwrap_or(zone);
+    let relative = Path::new(name);
+    if name.is_empty()
+        || !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+    {
+        return false;
+    }
+    let tzdir = env::var_os("TZDIR").map(PathBuf::from);
+    tzdir
+        .into_iter()
+        .chain(
+            [
+                "/usr/share/zoneinfo",
+                "/usr/lib/zoneinfo",
+                "/usr/share/lib/zoneinfo",
+            ]
+            .iter()
+            .map(PathBuf::from),
+        )
+        .any(|dir| {
+            // Zone files start with the `TZif` magic, unlike `zone.tab` and
+            // the other files of the database
+            let mut magic = [0; 4];
+            File::open(dir.join(relative))
+                .and_then(|mut file| file.read_exact(&mut magic))
+                .map_or(false, |()| &magic == b"TZif")
+        })
+}
+
+/// Whether a zone is a POSIX TZ rule such as `EST5EDT` or `<+0330>-3:30`,
+/// which the C library interprets without the time zone database.
+fn is_posix_zone(zone: &str) -> bool {
+    let offset = match zone.strip_prefix('<') {
+        Some(quoted) => quoted.split_once('>').map(|(_, rest)| rest),
+        None => {
+            let name_len = zone.bytes().take_while(u8::is_ascii_alphabetic).count();
+            Some(&zone[name_len..]).filter(|_| name_len >= 3)
+        }
+    };
+    let offset = offset.map(|offset| offset.strip_prefix(['+', '-']).unwrap_or(offset));
+    offset.map_or(false, |offset| {
+        offset.starts_with(|c: char| c.is_ascii_digit())
+    })
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut options = Options {
+        output: Output::Common(DateFormat::RFC3339),
+        zone: TimeZone::Utc,
+        unit: None,
+        rewrite: false,
+        input_zone: TimeZone::Utc,
+        inputs: Vec::new(),
+    };
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", name))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print!("{}", USAGE);
+                process::exit(0);
+            }
+            "-V" | "--version" => {
+                println!("time-format {}", env!("CARGO_PKG_VERSION"));
+                process::exit(0);
+            }
+            "-f" | "--format" => {
+                let format = value(&arg)?;
+                options.output = match DateFormat::from_name(&format) {
+                    Some(format) => Output::Common(format),
+                    None if format.contains('%') => Output::Strftime(
+                        CustomFormat::new(format.as_str())
+                            .map_err(|err| format!("{}: {}", format, err))?,
+                    ),
+                    None => return Err(format!("unknown format: {}", format)),
+                };
+            }
+            "-z" | "--zone" => options.zone = parse_zone(&value(&arg)?)?,
+            "-u" | "--unit" => {
+                let unit = value(&arg)?;
+                options.unit = match unit.as_str() {
+                    "auto" => None,
+                    _ => Some(
+                        unit.parse()
+                            .map_err(|_| format!("unknown unit: {}", unit))?,
+                    ),
+                }
+            }
+            "-r" | "--rewrite" => options.rewrite = true,
+            "--input-zone" => {
+                let zone = value(&arg)?;
+                // Named zones would also change the output zone
+                let named = !zone.starts_with(['+', '-'])
+                    && !matches!(zone.to_ascii_lowercase().as_str(), "utc" | "z" | "local");
+                if named {
+                    return Err(format!("unsupported input zone: {}", zone));
+                }
+                options.input_zone = parse_zone(&zone)?;
+            }
+            "--" => options.inputs.extend(args.by_ref()),
+            _ if arg.starts_with('-') && arg.len() > 1 && !is_number(&arg) => {
+                return Err(format!("unknown option: {}", arg))
+            }
+            _ => options.inputs.push(arg),
+        }
+    }
+    Ok(options)
+}
+
+fn is_number(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+}
+
+fn parse_integer(s: &str, unit: Option<EpochUnit>) -> Result<TimeStampMs, Error> {
+    let value: i64 = s.parse().map_err(|_| Error::InvalidTimestamp)?;
+    match unit {
+        Some(unit) => Ok(unit.to_timestamp_ms(value)),
+        // Values that are a date in no unit, such as 0, are seconds
+        None => match EpochDetector::default().detect(value) {
+            Ok((_, ts_ms)) => Ok(ts_ms),
+            Err(Error::InvalidTimestamp) => Ok(EpochUnit::Seconds.to_timestamp_ms(value)),
+            Err(err) => Err(err),
+        },
+    }
+}
+
+fn parse_timestamp(input: &str, unit: Option<EpochUnit>) -> Result<TimeStampMs, Error> {
+    let input = input.trim();
+    let digits = input.strip_prefix(['-', '+']).unwrap_or(input);
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+        return parse_integer(input, unit);
+    }
+    input
+        .parse::<TimeStampMs>()
+        .or_else(|_| time_format::parse_http_date(input).map(TimeStampMs::from_timestamp))
+        .map_err(|_| Error::ParseError)
+}
+
+fn format(options: &Options, ts_ms: TimeStampMs) -> Result<String, Error> {
+    options
+        .output
+        .format_ref()
+        .display_in_zone(ts_ms, options.zone)
+        .try_to_string()
+}
+
+fn rewrite(options: Options) -> ! {
+    let rewriter = Rewriter::new(options.output.format_ref())
+        .with_zone(options.zone)
+        .with_input_zone(options.input_zone);
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let result = if options.inputs.is_empty() {
+        rewriter.rewrite(io::stdin().lock(), &mut out)
+    } else {
+        options.inputs.iter().try_fold(0, |count, path| {
+            let file = File::open(path)
+                .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path, err)))?;
+            Ok(count + rewriter.rewrite(BufReader::new(file), &mut out)?)
+        })
+    };
+    match result {
+        Ok(_) => process::exit(0),
+        Err(err) => {
+            eprintln!("time-format: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let options = match parse_args() {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("time-format: {}", message);
+            eprintln!("Try 'time-format --help' for more information.");
+            process::exit(2);
+        }
+    };
+    if options.rewrite {
+        rewrite(options);
+    }
+
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let mut failed = false;
+    let mut convert = |input: &str| {
+        if input.trim().is_empty() {
+            return;
+        }
+        match parse_timestamp(input, options.unit).and_then(|ts_ms| format(&options, ts_ms)) {
+            Ok(formatted) => {
+                if writeln!(out, "{}", formatted).is_err() {
+                    process::exit(1);
+                }
+            }
+            Err(err) => {
+                eprintln!("time-format: {}: {}", input.trim(), err);
+                failed = true;
+            }
+        }
+    };
+
+    if options.inputs.is_empty() {
+        for line in io::stdin().lock().lines() {
+            match line {
+                Ok(line) => convert(&line),
+                Err(err) => {
+                    eprintln!("time-format: {}", err);
+                    process::exit(1);
+                }
+            }
+        }
+    } else {
+        for input in &options.inputs {
+            convert(input);
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,167 @@
+//! Per-thread cache of the rendering of the current second, shared by the
+//! `log` and `tracing-subscriber` integrations so that only the fractional
+//! part is formatted for most timestamps.
+
+use std::{cell::RefCell, fmt};
+
+use crate::{
+    check_format,
+    strftime::{self, StackBuf, Token},
+    tm, tm_in_zone, write_tokens, DateFormat, Error, FormatRef, Fraction, Locale, Precision,
+    TimeZone,
+};
+
+/// A date format, time zone and precision to render timestamps with.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CachedFormat<'a> {
+    pub format: FormatRef<'a>,
+    pub zone: TimeZone,
+    pub precision: Precision,
+}
+
+/// A format kept by the cache to compare with, owning the format string of
+/// custom formats.
+#[derive(Debug, PartialEq)]
+enum OwnedFormat {
+    Date(DateFormat),
+    Custom(String),
+}
+
+impl OwnedFormat {
+    fn new(format: FormatRef<'_>) -> Self {
+        match format {
+            FormatRef::Date(format) => Self::Date(format),
+            FormatRef::Custom(format) => Self::Custom(format.as_str().to_owned()),
+        }
+    }
+
+    fn is(&self, format: FormatRef<'_>) -> bool {
+        match (self, format) {
+            (Self::Date(owned), FormatRef::Date(format)) => *owned == format,
+            (Self::Custom(owned), FormatRef::Custom(format)) => owned == format.as_str(),
+            _ => false,
+        }
+    }
+}
+
+/// Rendering of the last second formatted on this thread, split where the
+/// fractional seconds go.
+struct Cached {
+    format: OwnedFormat,
+    zone: TimeZone,
+    precision: Precision,
+    seconds: i64,
+    segments: Vec<String>,
+}
+
+impl Cached {
+    fn is_for(&self, format: &CachedFormat<'_>, seconds: i64) -> bool {
+        self.seconds == seconds
+            && self.zone == format.zone
+            && self.precision == format.precision
+            && self.format.is(format.format)
+    }
+}
+
+thread_local! {
+    static CACHE: RefCell<Option<Cached>> = const { RefCell::new(None) };
+}
+
+impl CachedFormat<'_> {
+    /// Write a timestamp with fractional seconds for its `{ms}` placeholders.
+    ///
+    /// The cache is only borrowed while rendering into a stack buffer, not
+    /// while writing to `w`, so that writers may write timestamps
+    /// themselves.
+    pub fn write<W: fmt::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        seconds: i64,
+        fraction: Fraction,
+    ) -> Result<(), Error> {
+        let mut buf = StackBuf::new();
+        let cached = CACHE.with(|cache| {
+            let mut cache = cache.try_borrow_mut().ok()?;
+            match cache.as_ref() {
+                Some(cached) if cached.is_for(self, seconds) => {}
+                _ => {
+                    *cache = Some(Cached {
+                        format: OwnedFormat::new(self.format),
+                        zone: self.zone,
+                        precision: self.precision,
+                        seconds,
+                        segments: self.render_segments(seconds).ok()?,
+                    })
+                }
+            }
+            // Renderings too long for the buffer take the uncached path
+            Self::write_segments(&mut buf, &cache.as_ref()?.segments, fraction).ok()
+        });
+        match cached {
+            Some(()) => w.write_str(buf.as_str()).map_err(|_| Error::FormatError),
+            None => {
+                let segments = self.render_segments(seconds)?;
+                Self::write_segments(w, &segments, fraction).map_err(|_| Error::FormatError)
+            }
+        }
+    }
+
+    /// Join the segments with the fractional part in between.
+    fn write_segments<W: fmt::Write + ?Sized>(
+        out: &mut W,
+        segments: &[String],
+        fraction: Fraction,
+    ) -> fmt::Result {
+        for (i, segment) in segments.iter().enumerate() {
+            if i > 0 {
+                write!(out, "{}", fraction)?;
+            }
+            out.write_str(segment)?;
+        }
+        Ok(())
+    }
+
+    /// Render the parts of the format around the fractional seconds.
+    fn render_segments(&self, seconds: i64) -> Result<Vec<String>, Error> {
+        let tm = tm_in_zone(seconds, self.format.zone_for(self.zone))?;
+        let locale = self.format.locale();
+        match self.format {
+            FormatRef::Date(format) => {
+                let pattern = format.pattern_for(self.precision);
+                check_format(pattern)?;
+                // The format was validated, so that tokenizing can't fail
+                let tokens = strftime::tokens(pattern).map_while(Result::ok);
+                split_segments(tokens, &tm, locale)
+            }
+            FormatRef::Custom(format) => split_segments(format.tokens(), &tm, locale),
+        }
+    }
+}
+
+/// Render tokens into the segments between `{ms}` placeholders.
+fn split_segments<'t>(
+    mut tokens: impl Iterator<Item = Token<'t>>,
+    tm: &tm,
+    locale: Option<&Locale>,
+) -> Result<Vec<String>, Error> {
+    let mut segments = Vec::new();
+    loop {
+        let mut split = false;
+        let segment_tokens = tokens.by_ref().take_while(|token| {
+            split = matches!(token, Token::Millis);
+            !split
+        });
+        let mut segment = String::new();
+        write_tokens(
+            &mut segment,
+            segment_tokens,
+            tm,
+            Fraction::millis(0),
+            locale,
+        )?;
+        segments.push(segment);
+        if !split {
+            return Ok(segments);
+        }
+    }
+}
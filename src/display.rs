@@ -0,0 +1,231 @@
+//! `fmt::Display` adapters, to interpolate timestamps in `format!`,
+//! `write!` and logging macros. They render through fixed-size stack
+//! buffers straight into the formatter, and only allocate for unusually
+//! long renderings.
+
+use std::fmt;
+
+use crate::{
+    write_strftime, DateFormat, Error, FormatRef, Fraction, TimeStamp, TimeStampMs, TimeZone,
+};
+
+/// A timestamp rendered with a strftime format string.
+///
+/// Created by [`TimeStampMs::display_utc`], [`TimeStampMs::display_local`],
+/// [`TimeStampMs::iso8601`] and [`TimeStampMs::iso8601_local`].
+///
+/// Formatting fails with [`fmt::Error`] if the format string is invalid,
+/// which makes `to_string()` panic: use [`validate_format`](crate::validate_format)
+/// first if the format comes from an untrusted source.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayTime<'a> {
+    format: &'a str,
+    ts_ms: TimeStampMs,
+    zone: TimeZone,
+}
+
+impl DisplayTime<'_> {
+    /// Render into a new string, returning the error that makes `Display`
+    /// fail if the format string or the timestamp are invalid.
+    pub fn try_to_string(&self) -> Result<String, Error> {
+        let mut out = String::new();
+        self.write_to(&mut out)?;
+        Ok(out)
+    }
+
+    fn write_to<W: fmt::Write + ?Sized>(&self, out: &mut W) -> Result<(), Error> {
+        let fraction = Fraction::millis(self.ts_ms.milliseconds);
+        write_strftime(
+            out,
+            self.format,
+            self.ts_ms.seconds,
+            fraction,
+            self.zone,
+            None,
+        )
+    }
+}
+
+impl fmt::Display for DisplayTime<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f).map_err(|_| fmt::Error)
+    }
+}
+
+/// A timestamp rendered with a [`DateFormat`] or a [`CustomFormat`](crate::CustomFormat).
+///
+/// Created by [`DateFormat::display`], [`FormatRef::display`] and their
+/// variants.
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayDate<'a> {
+    format: FormatRef<'a>,
+    ts_seconds: TimeStamp,
+    fraction: Option<Fraction>,
+    zone: TimeZone,
+}
+
+impl DisplayDate<'_> {
+    /// Render into a new string, returning the error that makes `Display`
+    /// fail if the timestamp can't be represented.
+    pub fn try_to_string(&self) -> Result<String, Error> {
+        self.format
+            .format(self.ts_seconds, self.fraction, self.zone)
+    }
+}
+
+impl fmt::Display for DisplayDate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format
+            .write_to(f, self.ts_seconds, self.fraction, self.zone)
+            .map_err(|_| fmt::Error)
+    }
+}
+
+impl TimeStampMs {
+    /// Display the timestamp in the UTC time zone, with a strftime format
+    /// string supporting the `{ms}` placeholder.
+    ///
+    /// ```rust
+    /// use time_format::TimeStampMs;
+    ///
+    /// let ts_ms = TimeStampMs::new(1747751445, 123);
+    /// assert_eq!(format!("[{}]", ts_ms.display_utc("%F %T.{ms}")), "[2025-05-20 14:30:45.123]");
+    /// ```
+    pub fn display_utc<'a>(&self, format: &'a str) -> DisplayTime<'a> {
+        self.display_in_zone(format, TimeZone::Utc)
+    }
+
+    /// Display the timestamp in the local time zone, with a strftime format
+    /// string supporting the `{ms}` placeholder.
+    pub fn display_local<'a>(&self, format: &'a str) -> DisplayTime<'a> {
+        self.display_in_zone(format, TimeZone::Local)
+    }
+
+    /// Display the timestamp in the given time zone, with a strftime format
+    /// string supporting the `{ms}` placeholder.
+    ///
+    /// ```rust
+    /// use time_format::{TimeStampMs, TimeZone};
+    ///
+    /// let ts_ms = TimeStampMs::new(1747751445, 123);
+    /// let ist = ts_ms.display_in_zone("%H:%M:%S %z", TimeZone::FixedOffset(19800));
+    /// assert_eq!(ist.to_string(), "20:00:45 +0530");
+    /// ```
+    pub fn display_in_zone<'a>(&self, format: &'a str, zone: TimeZone) -> DisplayTime<'a> {
+        DisplayTime {
+            format,
+            ts_ms: *self,
+            zone,
+        }
+    }
+
+    /// Display the timestamp in ISO 8601 format in UTC, with millisecond
+    /// precision, as [`format_iso8601_ms_utc`](crate::format_iso8601_ms_utc) does.
+    ///
+    /// ```rust
+    /// use time_format::TimeStampMs;
+    ///
+    /// let ts_ms = TimeStampMs::new(1747751445, 123);
+    /// assert_eq!(ts_ms.iso8601().to_string(), "2025-05-20T14:30:45.123Z");
+    /// ```
+    pub fn iso8601(&self) -> DisplayTime<'static> {
+        self.display_utc("%Y-%m-%dT%H:%M:%S.{ms}Z")
+    }
+
+    /// Display the timestamp in ISO 8601 format in the local time zone, with
+    /// millisecond precision, as [`format_iso8601_ms_local`](crate::format_iso8601_ms_local) does.
+    pub fn iso8601_local(&self) -> DisplayTime<'static> {
+        self.display_local("%Y-%m-%dT%H:%M:%S.{ms}%:z")
+    }
+}
+
+impl DateFormat {
+    /// Display a timestamp in this format in the UTC time zone, as
+    /// [`format_common_utc`](crate::format_common_utc) does.
+    ///
+    /// ```rust
+    /// use time_format::DateFormat;
+    ///
+    /// let ts = 1747751445;
+    /// let header = format!("Last-Modified: {}", DateFormat::HTTP.display(ts));
+    /// assert_eq!(header, "Last-Modified: Tue, 20 May 2025 14:30:45 GMT");
+    /// ```
+    pub fn display(self, ts: TimeStamp) -> DisplayDate<'static> {
+        FormatRef::from(self).display(ts)
+    }
+
+    /// Display a timestamp in this format in the local time zone, as
+    /// [`format_common_local`](crate::format_common_local) does.
+    pub fn display_local(self, ts: TimeStamp) -> DisplayDate<'static> {
+        FormatRef::from(self).display_local(ts)
+    }
+
+    /// Display a timestamp with millisecond precision in this format in the
+    /// UTC time zone, as [`format_common_ms_utc`](crate::format_common_ms_utc) does.
+    pub fn display_ms(self, ts_ms: TimeStampMs) -> DisplayDate<'static> {
+        FormatRef::from(self).display_ms(ts_ms)
+    }
+
+    /// Display a timestamp with millisecond precision in this format in the
+    /// local time zone, as [`format_common_ms_local`](crate::format_common_ms_local) does.
+    pub fn display_ms_local(self, ts_ms: TimeStampMs) -> DisplayDate<'static> {
+        FormatRef::from(self).display_ms_local(ts_ms)
+    }
+
+    /// Display a timestamp with millisecond precision in this format in the
+    /// given time zone. HTTP dates ignore it and always use UTC.
+    pub fn display_in_zone(self, ts_ms: TimeStampMs, zone: TimeZone) -> DisplayDate<'static> {
+        FormatRef::from(self).display_in_zone(ts_ms, zone)
+    }
+}
+
+impl<'a> FormatRef<'a> {
+    /// Display a timestamp in this format in the UTC time zone, as
+    /// [`format_common_utc`](crate::format_common_utc) does.
+    pub fn display(self, ts: TimeStamp) -> DisplayDate<'a> {
+        DisplayDate {
+            format: self,
+            ts_seconds: ts,
+            fraction: None,
+            zone: TimeZone::Utc,
+        }
+    }
+
+    /// Display a timestamp in this format in the local time zone, as
+    /// [`format_common_local`](crate::format_common_local) does.
+    pub fn display_local(self, ts: TimeStamp) -> DisplayDate<'a> {
+        DisplayDate {
+            zone: TimeZone::Local,
+            ..self.display(ts)
+        }
+    }
+
+    /// Display a timestamp with millisecond precision in this format in the
+    /// UTC time zone, as [`format_common_ms_utc`](crate::format_common_ms_utc) does.
+    pub fn display_ms(self, ts_ms: TimeStampMs) -> DisplayDate<'a> {
+        DisplayDate {
+            format: self,
+            ts_seconds: ts_ms.seconds,
+            fraction: Some(Fraction::millis(ts_ms.milliseconds)),
+            zone: TimeZone::Utc,
+        }
+    }
+
+    /// Display a timestamp with millisecond precision in this format in the
+    /// local time zone, as [`format_common_ms_local`](crate::format_common_ms_local) does.
+    pub fn display_ms_local(self, ts_ms: TimeStampMs) -> DisplayDate<'a> {
+        DisplayDate {
+            zone: TimeZone::Local,
+            ..self.display_ms(ts_ms)
+        }
+    }
+
+    /// Display a timestamp with millisecond precision in this format in the
+    /// given time zone. HTTP dates ignore it and always use UTC.
+    pub fn display_in_zone(self, ts_ms: TimeStampMs, zone: TimeZone) -> DisplayDate<'a> {
+        DisplayDate {
+            zone,
+            ..self.display_ms(ts_ms)
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Human-readable formatting and parsing of time spans.
+
+use std::{convert::TryFrom, fmt::Write, time::Duration};
+
+use crate::Error;
+
+const NANOS_PER_MICRO: u128 = 1_000;
+const NANOS_PER_MILLI: u128 = 1_000_000;
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+const NANOS_PER_MIN: u128 = 60 * NANOS_PER_SEC;
+const NANOS_PER_HOUR: u128 = 60 * NANOS_PER_MIN;
+const NANOS_PER_DAY: u128 = 24 * NANOS_PER_HOUR;
+const NANOS_PER_WEEK: u128 = 7 * NANOS_PER_DAY;
+
+/// Style used to render a duration.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DurationStyle {
+    /// Compact form, as accepted by [`parse_duration`]: "1h23m4.5s", "250ms", "2d"
+    Compact,
+    /// Long form with the two most significant units: "1 hour, 23 minutes"
+    Long,
+    /// Clock form with millisecond precision: "01:23:04.500"
+    ///
+    /// Hours are not wrapped, so a span of two days renders as "48:00:00.000".
+    Clock,
+}
+
+/// Format a duration in the given style.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use time_format::{format_duration, DurationStyle};
+///
+/// let d = Duration::from_millis(4_984_500);
+/// assert_eq!(format_duration(d, DurationStyle::Compact), "1h23m4.5s");
+/// assert_eq!(format_duration(d, DurationStyle::Long), "1 hour, 23 minutes");
+/// assert_eq!(format_duration(d, DurationStyle::Clock), "01:23:04.500");
+///
+/// assert_eq!(format_duration(Duration::from_millis(250), DurationStyle::Compact), "250ms");
+/// ```
+pub fn format_duration(duration: Duration, style: DurationStyle) -> String {
+    format_nanos(false, duration.as_nanos(), style)
+}
+
+/// Format a signed span of milliseconds in the given style.
+///
+/// Negative spans are prefixed with `-`.
+///
+/// ```rust
+/// use time_format::{format_duration_ms, DurationStyle};
+///
+/// assert_eq!(format_duration_ms(-90_000, DurationStyle::Compact), "-1m30s");
+/// assert_eq!(format_duration_ms(-90_000, DurationStyle::Clock), "-00:01:30.000");
+/// ```
+pub fn format_duration_ms(ms: i64, style: DurationStyle) -> String {
+    format_nanos(
+        ms < 0,
+        u128::from(ms.unsigned_abs()) * NANOS_PER_MILLI,
+        style,
+    )
+}
+
+fn format_nanos(negative: bool, nanos: u128, style: DurationStyle) -> String {
+    let mut out = String::new();
+    if negative && nanos > 0 {
+        out.push('-');
+    }
+    match style {
+        DurationStyle::Compact => format_compact(&mut out, nanos),
+        DurationStyle::Long => format_long(&mut out, nanos),
+        DurationStyle::Clock => format_clock(&mut out, nanos),
+    }
+    out
+}
+
+/// Write `value / unit` as a decimal number, without trailing zeros.
+fn write_decimal(out: &mut String, value: u128, unit: u128) {
+    let _ = write!(out, "{}", value / unit);
+    let mut frac = value % unit;
+    if frac > 0 {
+        out.push('.');
+        let mut scale = unit / 10;
+        while frac > 0 {
+            let _ = write!(out, "{}", frac / scale);
+            frac %= scale;
+            scale /= 10;
+        }
+    }
+}
+
+fn format_compact(out: &mut String, nanos: u128) {
+    if nanos == 0 {
+        out.push_str("0s");
+    } else if nanos < NANOS_PER_MICRO {
+        let _ = write!(out, "{}ns", nanos);
+    } else if nanos < NANOS_PER_MILLI {
+        write_decimal(out, nanos, NANOS_PER_MICRO);
+        out.push_str("us");
+    } else if nanos < NANOS_PER_SEC {
+        write_decimal(out, nanos, NANOS_PER_MILLI);
+        out.push_str("ms");
+    } else {
+        let mut rest = nanos;
+        for (unit, suffix) in [
+            (NANOS_PER_DAY, "d"),
+            (NANOS_PER_HOUR, "h"),
+            (NANOS_PER_MIN, "m"),
+        ] {
+            if rest >= unit {
+                let _ = write!(out, "{}{}", rest / unit, suffix);
+                rest %= unit;
+            }
+        }
+        if rest > 0 {
+            write_decimal(out, rest, NANOS_PER_SEC);
+            out.push('s');
+        }
+    }
+}
+
+fn format_long(out: &mut String, nanos: u128) {
+    const UNITS: [(u128, &str); 4] = [
+        (NANOS_PER_DAY, "day"),
+        (NANOS_PER_HOUR, "hour"),
+        (NANOS_PER_MIN, "minute"),
+        (NANOS_PER_SEC, "second"),
+    ];
+    const SUBSEC_UNITS: [(u128, &str); 3] = [
+        (NANOS_PER_MILLI, "millisecond"),
+        (NANOS_PER_MICRO, "microsecond"),
+        (1, "nanosecond"),
+    ];
+    let units = if nanos >= NANOS_PER_SEC {
+        &UNITS[..]
+    } else {
+        &SUBSEC_UNITS[..]
+    };
+
+    // Show the most significant unit, and the next one unless it is zero
+    let first = match units.iter().position(|&(unit, _)| nanos >= unit) {
+        Some(first) => first,
+        None => {
+            out.push_str("0 seconds");
+            return;
+        }
+    };
+    let mut rest = nanos;
+    for (i, &(unit, name)) in units[first..].iter().take(2).enumerate() {
+        let n = rest / unit;
+        rest %= unit;
+        if n == 0 {
+            break;
+        }
+        if i > 0 {
+            out.push_str(", ");
+        }
+        let _ = write!(out, "{} {}{}", n, name, if n == 1 { "" } else { "s" });
+    }
+}
+
+fn format_clock(out: &mut String, nanos: u128) {
+    let millis = nanos / NANOS_PER_MILLI;
+    let _ = write!(
+        out,
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    );
+}
+
+/// Parse a duration in compact form, such as `90m`, `1h30m`, `1.5s` or `250ms`.
+///
+/// The accepted units are `w`, `d`, `h`, `m`, `s`, `ms`, `us` (or `µs`) and
+/// `ns`. Every number needs a unit, except for a bare `0`. Numbers may have
+/// a fractional part, and components may be separated by spaces.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// assert_eq!(time_format::parse_duration("90m").unwrap(), Duration::from_secs(5400));
+/// assert_eq!(time_format::parse_duration("1h23m4.5s").unwrap(), Duration::from_millis(4_984_500));
+/// assert_eq!(time_format::parse_duration("1d 12h").unwrap(), Duration::from_secs(129_600));
+/// assert!(time_format::parse_duration("90").is_err());
+/// ```
+pub fn parse_duration(s: impl AsRef<str>) -> Result<Duration, Error> {
+    let (negative, nanos) = parse_nanos(s.as_ref())?;
+    if negative && nanos > 0 {
+        return Err(Error::ParseError);
+    }
+    let secs = u64::try_from(nanos / NANOS_PER_SEC).map_err(|_| Error::ParseError)?;
+    Ok(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+}
+
+/// Parse a signed duration in compact form into milliseconds.
+///
+/// The syntax is the one accepted by [`parse_duration`], with an optional
+/// leading `-` or `+`. Sub-millisecond precision is truncated.
+///
+/// ```rust
+/// assert_eq!(time_format::parse_duration_ms("-1m30s").unwrap(), -90_000);
+/// ```
+pub fn parse_duration_ms(s: impl AsRef<str>) -> Result<i64, Error> {
+    let (negative, nanos) = parse_nanos(s.as_ref())?;
+    let ms = i64::try_from(nanos / NANOS_PER_MILLI).map_err(|_| Error::ParseError)?;
+    Ok(if negative { -ms } else { ms })
+}
+
+fn parse_nanos(s: &str) -> Result<(bool, u128), Error> {
+    let s = s.trim();
+    let (negative, mut rest) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    if rest == "0" {
+        return Ok((negative, 0));
+    }
+    if rest.is_empty() {
+        return Err(Error::ParseError);
+    }
+
+    let mut total: u128 = 0;
+    while !rest.is_empty() {
+        let int_len = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (int_part, after) = rest.split_at(int_len);
+        let (frac_part, after) = match after.strip_prefix('.') {
+            Some(after) => {
+                let len = after
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(after.len());
+                after.split_at(len)
+            }
+            None => ("", after),
+        };
+        if int_part.is_empty() && frac_part.is_empty() {
+            return Err(Error::ParseError);
+        }
+        let unit_len = after
+            .find(|c: char| c.is_ascii_digit() || c.is_whitespace())
+            .unwrap_or(after.len());
+        let (unit, after) = after.split_at(unit_len);
+        let unit = match unit {
+            "w" => NANOS_PER_WEEK,
+            "d" => NANOS_PER_DAY,
+            "h" => NANOS_PER_HOUR,
+            "m" => NANOS_PER_MIN,
+            "s" => NANOS_PER_SEC,
+            "ms" => NANOS_PER_MILLI,
+            "us" | "µs" => NANOS_PER_MICRO,
+            "ns" => 1,
+            _ => return Err(Error::ParseError),
+        };
+
+        let int: u128 = if int_part.is_empty() {
+            0
+        } else {
+            int_part.parse().map_err(|_| Error::ParseError)?
+        };
+        let mut value = int.checked_mul(unit).ok_or(Error::ParseError)?;
+        let mut scale = unit;
+        for digit in frac_part.bytes() {
+            scale /= 10;
+            value += u128::from(digit - b'0') * scale;
+        }
+        total = total.checked_add(value).ok_or(Error::ParseError)?;
+        rest = after.trim_start();
+    }
+    Ok((negative, total))
+}
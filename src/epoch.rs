@@ -0,0 +1,171 @@
+//! Detection of the unit of integer epoch timestamps.
+
+use std::{fmt, str::FromStr};
+
+use crate::{Error, TimeStamp, TimeStampMs};
+
+/// Unit of an integer number counted from the UNIX epoch.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum EpochUnit {
+    /// Seconds, e.g. `1747751445`
+    Seconds,
+    /// Milliseconds, e.g. `1747751445123`
+    Milliseconds,
+    /// Microseconds, e.g. `1747751445123456`
+    Microseconds,
+    /// Nanoseconds, e.g. `1747751445123456789`
+    Nanoseconds,
+}
+
+impl EpochUnit {
+    /// All the units, from the coarsest to the finest.
+    pub const ALL: [EpochUnit; 4] = [
+        EpochUnit::Seconds,
+        EpochUnit::Milliseconds,
+        EpochUnit::Microseconds,
+        EpochUnit::Nanoseconds,
+    ];
+
+    /// Number of units per millisecond, or 0 for seconds.
+    fn per_ms(self) -> i64 {
+        match self {
+            EpochUnit::Seconds => 0,
+            EpochUnit::Milliseconds => 1,
+            EpochUnit::Microseconds => 1_000,
+            EpochUnit::Nanoseconds => 1_000_000,
+        }
+    }
+
+    /// Convert a number in this unit to a timestamp. Precision finer than
+    /// milliseconds is truncated, towards the past.
+    ///
+    /// ```rust
+    /// use time_format::{EpochUnit, TimeStampMs};
+    ///
+    /// let ts_ms = EpochUnit::Microseconds.to_timestamp_ms(1747751445123456);
+    /// assert_eq!(ts_ms, TimeStampMs::new(1747751445, 123));
+    /// ```
+    pub fn to_timestamp_ms(self, value: i64) -> TimeStampMs {
+        match self.per_ms() {
+            0 => TimeStampMs::from_timestamp(value),
+            per_ms => {
+                let ms = value.div_euclid(per_ms);
+                TimeStampMs::new(ms.div_euclid(1000), ms.rem_euclid(1000) as u16)
+            }
+        }
+    }
+
+    /// Short name of the unit: `s`, `ms`, `us` or `ns`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EpochUnit::Seconds => "s",
+            EpochUnit::Milliseconds => "ms",
+            EpochUnit::Microseconds => "us",
+            EpochUnit::Nanoseconds => "ns",
+        }
+    }
+}
+
+impl fmt::Display for EpochUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EpochUnit {
+    type Err = Error;
+
+    /// Parse a short name (`s`, `ms`, `us` or `µs`, `ns`) or a plural name
+    /// such as `seconds`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let unit = match s.to_lowercase().as_str() {
+            "s" | "sec" | "secs" | "second" | "seconds" => EpochUnit::Seconds,
+            "ms" | "milli" | "millis" | "millisecond" | "milliseconds" => EpochUnit::Milliseconds,
+            "us" | "µs" | "micro" | "micros" | "microsecond" | "microseconds" => {
+                EpochUnit::Microseconds
+            }
+            "ns" | "nano" | "nanos" | "nanosecond" | "nanoseconds" => EpochUnit::Nanoseconds,
+            _ => return Err(Error::ParseError),
+        };
+        Ok(unit)
+    }
+}
+
+/// Classifier of integer epoch timestamps by magnitude.
+///
+/// A value is read in each unit in turn, and the units that put it within
+/// a plausible window of dates are kept: 2000 to 2100 by default, in which
+/// the four units never overlap. A wider window can make values ambiguous,
+/// which [`EpochDetector::detect`] reports as an error rather than guessing.
+///
+/// ```rust
+/// use time_format::{EpochDetector, EpochUnit, Error, TimeStampMs};
+///
+/// let detector = EpochDetector::default();
+/// assert_eq!(
+///     detector.detect(1747751445123).unwrap(),
+///     (EpochUnit::Milliseconds, TimeStampMs::new(1747751445, 123))
+/// );
+/// assert_eq!(detector.detect(1747751445123456789).unwrap().0, EpochUnit::Nanoseconds);
+/// assert_eq!(detector.detect(42), Err(Error::InvalidTimestamp));
+///
+/// // Since 1970, a trillion is a date as milliseconds, but also as
+/// // microseconds and nanoseconds
+/// let detector = EpochDetector::new(0, 4102444800);
+/// assert_eq!(detector.detect(1_000_000_000_000), Err(Error::AmbiguousTimestamp));
+/// let units: Vec<_> = detector.candidates(1_000_000_000_000).map(|(unit, _)| unit).collect();
+/// assert_eq!(units, [EpochUnit::Milliseconds, EpochUnit::Microseconds, EpochUnit::Nanoseconds]);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct EpochDetector {
+    min: TimeStamp,
+    max: TimeStamp,
+}
+
+impl Default for EpochDetector {
+    /// Dates from 2000-01-01 (included) to 2100-01-01 (excluded).
+    fn default() -> Self {
+        Self::new(946_684_800, 4_102_444_800)
+    }
+}
+
+impl EpochDetector {
+    /// A detector for dates from `min` (included) to `max` (excluded), in
+    /// seconds since the epoch.
+    pub fn new(min: TimeStamp, max: TimeStamp) -> Self {
+        Self { min, max }
+    }
+
+    /// The window of plausible dates, in seconds since the epoch.
+    pub fn range(&self) -> (TimeStamp, TimeStamp) {
+        (self.min, self.max)
+    }
+
+    /// Whether a timestamp is in the window of plausible dates.
+    pub fn contains(&self, ts_ms: TimeStampMs) -> bool {
+        ts_ms.seconds >= self.min && ts_ms.seconds < self.max
+    }
+
+    /// The units that put a value in the window, from the coarsest to the
+    /// finest, with the corresponding timestamps.
+    pub fn candidates(&self, value: i64) -> impl Iterator<Item = (EpochUnit, TimeStampMs)> {
+        let detector = *self;
+        EpochUnit::ALL
+            .iter()
+            .map(move |&unit| (unit, unit.to_timestamp_ms(value)))
+            .filter(move |&(_, ts_ms)| detector.contains(ts_ms))
+    }
+
+    /// The unit of a value and the corresponding timestamp.
+    ///
+    /// Fails with [`Error::InvalidTimestamp`] if no unit puts the value in
+    /// the window, and with [`Error::AmbiguousTimestamp`] if several do.
+    pub fn detect(&self, value: i64) -> Result<(EpochUnit, TimeStampMs), Error> {
+        let mut candidates = self.candidates(value);
+        let first = candidates.next().ok_or(Error::InvalidTimestamp)?;
+        match candidates.next() {
+            Some(_) => Err(Error::AmbiguousTimestamp),
+            None => Ok(first),
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! Inference of the strftime format of sample date strings.
+
+use crate::{
+    parse::{parse_with_format, parse_with_format_offset},
+    write_strftime, DateFormat, Error, Fraction, Locale, TimeStampMs, TimeZone,
+};
+
+/// Maximum number of candidate patterns generated for a single sample.
+const MAX_CANDIDATES: usize = 64;
+
+/// A strftime pattern inferred from sample strings, returned by
+/// [`infer_format`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatCandidate {
+    /// The strftime pattern, with the `{ms}` placeholder for fractional
+    /// seconds.
+    pub pattern: String,
+    /// The built-in format with the same pattern, if any.
+    pub date_format: Option<DateFormat>,
+    /// Number of samples that the pattern parses.
+    pub matched: usize,
+    /// Confidence from 0 to 1: the share of the samples that the pattern
+    /// parses, counting half for those that it doesn't render back
+    /// identically, divided between the candidates with the same score.
+    pub confidence: f64,
+}
+
+impl FormatCandidate {
+    /// Parse a string with the pattern, as
+    /// [`parse_with_format`](crate::parse_with_format) does.
+    pub fn parse(&self, s: impl AsRef<str>) -> Result<TimeStampMs, Error> {
+        parse_with_format(s.as_ref(), &self.pattern)
+    }
+}
+
+/// Infer the format of sample date strings, such as the values of a column.
+///
+/// Candidate patterns are generated from the shape of each sample, then
+/// tried on all the samples: a pattern scores a point for each sample that
+/// it parses and renders back identically, and half a point for each
+/// sample that it only parses. The candidates are returned from the most
+/// to the least likely, and those that parse none of the samples are
+/// left out.
+///
+/// A single sample such as `01/02/2025` can't tell days from months, so
+/// both orders are returned with the same confidence; other samples such
+/// as `13/02/2025` settle it.
+///
+/// ```rust
+/// use time_format::{infer_format, DateFormat};
+///
+/// let candidates = infer_format(&["01/02/2025 10:00:00", "13/02/2025 18:30:00"]);
+/// assert_eq!(candidates[0].pattern, "%d/%m/%Y %H:%M:%S");
+/// assert_eq!(candidates[0].date_format, Some(DateFormat::European));
+/// assert_eq!(candidates[0].confidence, 1.0);
+///
+/// let ts_ms = candidates[0].parse("20/05/2025 14:30:45").unwrap();
+/// assert_eq!(ts_ms.seconds, 1747751445);
+///
+/// // Ambiguous
+/// let candidates = infer_format(&["01/02/2025"]);
+/// assert_eq!(candidates[0].pattern, "%m/%d/%Y");
+/// assert_eq!(candidates[1].pattern, "%d/%m/%Y");
+/// assert_eq!(candidates[1].confidence, 0.5);
+///
+/// let candidates = infer_format(&["May  5 14:30:45.123456", "May 20 01:02:03.000001"]);
+/// assert_eq!(candidates[0].pattern, "%b %e %H:%M:%S.{ms}");
+/// ```
+pub fn infer_format<S: AsRef<str>>(samples: &[S]) -> Vec<FormatCandidate> {
+    let samples: Vec<&str> = samples.iter().map(|s| s.as_ref().trim()).collect();
+    let mut patterns: Vec<String> = Vec::new();
+    for sample in &samples {
+        for pattern in candidate_patterns(sample) {
+            // Patterns without directives only match their own sample
+            let literal = !pattern.replace("%%", "").contains('%');
+            if !literal && !patterns.contains(&pattern) {
+                patterns.push(pattern);
+            }
+        }
+    }
+
+    let mut candidates: Vec<(FormatCandidate, u32)> = patterns
+        .into_iter()
+        .filter_map(|pattern| {
+            let mut matched = 0;
+            // Half points, to keep the scores exact
+            let mut score = 0;
+            for sample in &samples {
+                match round_trip(sample, &pattern) {
+                    Some(true) => score += 2,
+                    Some(false) => score += 1,
+                    None => continue,
+                }
+                matched += 1;
+            }
+            if matched == 0 {
+                return None;
+            }
+            let date_format = DateFormat::ALL
+                .iter()
+                .find(|format| format.pattern(false) == pattern || format.pattern(true) == pattern)
+                .copied();
+            let candidate = FormatCandidate {
+                pattern,
+                date_format,
+                matched,
+                confidence: 0.0,
+            };
+            Some((candidate, score))
+        })
+        .collect();
+
+    let max_score = (2 * samples.len()) as f64;
+    for i in 0..candidates.len() {
+        let score = candidates[i].1;
+        let ties = candidates.iter().filter(|(_, s)| *s == score).count();
+        candidates[i].0.confidence = f64::from(score) / max_score / ties as f64;
+    }
+    // Stable, so that ties keep the order in which they were generated
+    candidates.sort_by(|(a, _), (b, _)| b.confidence.total_cmp(&a.confidence));
+    candidates
+        .into_iter()
+        .map(|(candidate, _)| candidate)
+        .collect()
+}
+
+/// Parse a sample with a pattern, and tell whether rendering the result
+/// gives the sample back.
+fn round_trip(sample: &str, pattern: &str) -> Option<bool> {
+    let (ts_ms, offset) = parse_with_format_offset(sample, pattern).ok()?;
+    let zone = offset.map_or(TimeZone::Utc, TimeZone::FixedOffset);
+    let mut rendered = String::with_capacity(sample.len());
+    if write_strftime(
+        &mut rendered,
+        pattern,
+        ts_ms.seconds,
+        Fraction::millis(ts_ms.milliseconds),
+        zone,
+        None,
+    )
+    .is_err()
+    {
+        return Some(false);
+    }
+    // `{ms}` always renders three digits, whatever the length of the
+    // fraction in the sample
+    if pattern.contains("{ms}") {
+        return Some(strip_fractions(&rendered) == strip_fractions(sample));
+    }
+    Some(rendered == sample)
+}
+
+/// Remove the digits of the fractional seconds: those after a `.` or a `,`
+/// that follows a digit.
+fn strip_fractions(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut prev = None;
+    let mut in_fraction = false;
+    for c in s.chars() {
+        if c.is_ascii_digit() && in_fraction {
+            continue;
+        }
+        in_fraction = matches!(c, '.' | ',') && prev.map_or(false, |p: char| p.is_ascii_digit());
+        out.push(c);
+        prev = Some(c);
+    }
+    out
+}
+
+/// A piece of a sample string.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Piece<'a> {
+    Digits(&'a str),
+    Word(&'a str),
+    Other(char),
+}
+
+fn split(sample: &str) -> Vec<Piece<'_>> {
+    let mut pieces = Vec::new();
+    let mut rest = sample;
+    while let Some(c) = rest.chars().next() {
+        let len = if c.is_ascii_digit() {
+            rest.bytes().take_while(u8::is_ascii_digit).count()
+        } else if c.is_alphabetic() {
+            rest.find(|c: char| !c.is_alphabetic())
+                .unwrap_or(rest.len())
+        } else {
+            c.len_utf8()
+        };
+        let (piece, tail) = rest.split_at(len);
+        pieces.push(if c.is_ascii_digit() {
+            Piece::Digits(piece)
+        } else if c.is_alphabetic() {
+            Piece::Word(piece)
+        } else {
+            Piece::Other(c)
+        });
+        rest = tail;
+    }
+    pieces
+}
+
+/// Directive for a numeric field, without padding if the sample has none.
+fn numeric(conv: char, digits: &str) -> String {
+    if digits.len() == 1 {
+        format!("%-{}", conv)
+    } else {
+        format!("%{}", conv)
+    }
+}
+
+fn value(digits: &str) -> u32 {
+    digits.parse().unwrap_or(u32::MAX)
+}
+
+fn is_name(word: &str, names: &[&str]) -> bool {
+    names.iter().any(|name| name.eq_ignore_ascii_case(word))
+}
+
+/// Generate the patterns that the shape of a sample suggests, each of them
+/// as a list of alternatives for consecutive parts.
+fn candidate_patterns(sample: &str) -> Vec<String> {
+    let en = &Locale::EN;
+    let pieces = split(sample);
+    let twelve_hour = pieces
+        .iter()
+        .any(|piece| matches!(piece, Piece::Word(word) if is_name(word, &en.am_pm)));
+    let month_name = pieces.iter().any(|piece| {
+        matches!(piece, Piece::Word(word) if is_name(word, &en.months) || is_name(word, &en.months_abbr))
+    });
+
+    let full_weekday = pieces
+        .iter()
+        .any(|piece| matches!(piece, Piece::Word(word) if is_name(word, &en.weekdays)));
+
+    let mut parts: Vec<Vec<String>> = Vec::new();
+    let mut seen_time = false;
+    let mut seen_day = false;
+    let mut i = 0;
+    while i < pieces.len() {
+        let rest = &pieces[i..];
+        let (alternatives, len) = match *rest {
+            // Epoch seconds, alone
+            [Piece::Digits(d), ref tail @ ..] if d.len() == 10 && i == 0 => match *tail {
+                [] => (vec!["%s".to_string()], 1),
+                [Piece::Other('.'), Piece::Digits(_)] => (vec!["%s.{ms}".to_string()], 3),
+                _ => (vec![d.to_string()], 1),
+            },
+            // Numeric dates
+            [Piece::Digits(a), Piece::Other(sep), Piece::Digits(b), Piece::Other(sep2), Piece::Digits(c), ..]
+                if sep == sep2
+                    && matches!(sep, '/' | '-' | '.')
+                    && a.len() <= 4
+                    && b.len() <= 2
+                    && c.len() <= 4 =>
+            {
+                seen_day = true;
+                let mut alternatives = Vec::new();
+                if a.len() == 4 {
+                    alternatives.push(format!(
+                        "%Y{}{}{}{}",
+                        sep,
+                        numeric('m', b),
+                        sep,
+                        numeric('d', c)
+                    ));
+                } else if a.len() <= 2 && matches!(c.len(), 2 | 4) {
+                    let year = if c.len() == 4 { "%Y" } else { "%y" };
+                    if value(a) <= 12 {
+                        alternatives.push(format!(
+                            "{}{}{}{}{}",
+                            numeric('m', a),
+                            sep,
+                            numeric('d', b),
+                            sep,
+                            year
+                        ));
+                    }
+                    if value(b) <= 12 {
+                        alternatives.push(format!(
+                            "{}{}{}{}{}",
+                            numeric('d', a),
+                            sep,
+                            numeric('m', b),
+                            sep,
+                            year
+                        ));
+                    }
+                }
+                if alternatives.is_empty() {
+                    (vec![a.to_string()], 1)
+                } else {
+                    (alternatives, 5)
+                }
+            }
+            // Times
+            [Piece::Digits(h), Piece::Other(':'), Piece::Digits(m), ref tail @ ..]
+                if h.len() <= 2 && m.len() == 2 =>
+            {
+                seen_time = true;
+                let hour = numeric(if twelve_hour { 'I' } else { 'H' }, h);
+                match *tail {
+                    [Piece::Other(':'), Piece::Digits(s), Piece::Other(dot), Piece::Digits(_), ..]
+                        if s.len() == 2 && matches!(dot, '.' | ',') =>
+                    {
+                        (vec![format!("{}:%M:%S{}{{ms}}", hour, dot)], 7)
+                    }
+                    [Piece::Other(':'), Piece::Digits(s), ..] if s.len() == 2 => {
+                        (vec![format!("{}:%M:%S", hour)], 5)
+                    }
+                    _ => (vec![format!("{}:%M", hour)], 3),
+                }
+            }
+            // ISO 8601 basic dates and times
+            [Piece::Digits(d), ..] if d.len() == 14 => {
+                seen_day = true;
+                seen_time = true;
+                (vec!["%Y%m%d%H%M%S".to_string()], 1)
+            }
+            [Piece::Digits(d), ..] if d.len() == 8 && !seen_day => {
+                seen_day = true;
+                (vec!["%Y%m%d".to_string()], 1)
+            }
+            [Piece::Digits(d), ref tail @ ..]
+                if matches!(d.len(), 4 | 6) && i > 0 && pieces[i - 1] == Piece::Word("T") =>
+            {
+                seen_time = true;
+                match *tail {
+                    [Piece::Other(dot), Piece::Digits(_), ..]
+                        if d.len() == 6 && matches!(dot, '.' | ',') =>
+                    {
+                        (vec![format!("%H%M%S{}{{ms}}", dot)], 3)
+                    }
+                    _ => {
+                        let pattern = if d.len() == 6 { "%H%M%S" } else { "%H%M" };
+                        (vec![pattern.to_string()], 1)
+                    }
+                }
+            }
+            // UTC offsets
+            [Piece::Other(sign), Piece::Digits(h), Piece::Other(':'), Piece::Digits(m), ..]
+                if seen_time && matches!(sign, '+' | '-') && h.len() == 2 && m.len() == 2 =>
+            {
+                (vec!["%:z".to_string()], 4)
+            }
+            [Piece::Other(sign), Piece::Digits(d), ..]
+                if seen_time && matches!(sign, '+' | '-') && matches!(d.len(), 2 | 4) =>
+            {
+                (vec!["%z".to_string()], 2)
+            }
+            // Space-padded days
+            [Piece::Other(' '), Piece::Other(' '), Piece::Digits(d), ..]
+                if d.len() == 1 && month_name && !seen_day =>
+            {
+                seen_day = true;
+                (vec![" %e".to_string()], 3)
+            }
+            // Years and days next to month names
+            [Piece::Digits(d), ..] if d.len() == 4 && month_name => (vec!["%Y".to_string()], 1),
+            [Piece::Digits(d), ..] if d.len() <= 2 && month_name && !seen_day => {
+                seen_day = true;
+                (vec![numeric('d', d)], 1)
+            }
+            [Piece::Word(word), ..] => {
+                // "May" is an abbreviation too, unless the weekday is in full
+                let alternatives = if is_name(word, &en.months_abbr) && !full_weekday {
+                    vec!["%b".to_string()]
+                } else if is_name(word, &en.months) {
+                    vec!["%B".to_string()]
+                } else if is_name(word, &en.weekdays) {
+                    vec!["%A".to_string()]
+                } else if is_name(word, &en.weekdays_abbr) {
+                    vec!["%a".to_string()]
+                } else if is_name(word, &en.am_pm) {
+                    vec!["%p".to_string()]
+                } else if seen_time && (word == "Z" || word == "UTC" || word == "GMT") {
+                    vec![
+                        word.to_string(),
+                        if word == "Z" { "%z" } else { "%Z" }.to_string(),
+                    ]
+                } else {
+                    vec![word.to_string()]
+                };
+                (alternatives, 1)
+            }
+            [Piece::Digits(d), ..] => (vec![d.to_string()], 1),
+            [Piece::Other(c), ..] => {
+                let literal = match c {
+                    '%' => "%%".to_string(),
+                    '{' => "{{".to_string(),
+                    '}' => "}}".to_string(),
+                    c => c.to_string(),
+                };
+                (vec![literal], 1)
+            }
+            [] => break,
+        };
+        parts.push(alternatives);
+        i += len;
+    }
+
+    let mut patterns = vec![String::new()];
+    for alternatives in parts {
+        // Past the limit, only the first alternative is kept
+        let alternatives = if patterns.len() * alternatives.len() > MAX_CANDIDATES {
+            &alternatives[..1]
+        } else {
+            &alternatives[..]
+        };
+        patterns = patterns
+            .iter()
+            .flat_map(|pattern| {
+                alternatives
+                    .iter()
+                    .map(move |alt| format!("{}{}", pattern, alt))
+            })
+            .collect();
+    }
+    patterns
+}
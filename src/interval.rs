@@ -0,0 +1,300 @@
+//! ISO 8601 time intervals (`start/end`, `start/duration`, `duration/end`)
+//! and recurring intervals (`Rn/...`).
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use crate::{
+    format_iso8601_ms_utc, format_iso8601_utc, parse, strftime_ms_utc, Error, IsoDuration,
+    TimeStampMs,
+};
+
+/// An ISO 8601 time interval.
+///
+/// Times without a UTC offset are interpreted as UTC. In the `start/end`
+/// form, the end may omit the leading components that are the same as the
+/// start's, as in `2025-05-20T10:00/12:00`.
+///
+/// ```rust
+/// use time_format::{Interval, TimeStampMs};
+///
+/// let interval: Interval = "2025-05-20T10:00Z/12:00".parse().unwrap();
+/// assert_eq!(interval.start().unwrap(), TimeStampMs::from_timestamp(1747735200));
+/// assert_eq!(interval.end().unwrap(), TimeStampMs::from_timestamp(1747742400));
+/// assert_eq!(interval.to_string(), "2025-05-20T10:00:00Z/2025-05-20T12:00:00Z");
+/// assert_eq!(interval.format_abbreviated().unwrap(), "2025-05-20T10:00:00Z/12:00:00");
+///
+/// let interval: Interval = "2025-05-20T10:00:00Z/PT2H".parse().unwrap();
+/// assert_eq!(interval.end().unwrap(), TimeStampMs::from_timestamp(1747742400));
+/// ```
+///
+/// The duration of an interval can't be negative:
+///
+/// ```rust
+/// use time_format::{Error, Interval};
+///
+/// assert_eq!("2025-05-20T10:00:00Z/-PT2H".parse::<Interval>(), Err(Error::InvalidTimestamp));
+/// assert_eq!("-PT2H/2025-05-20T10:00:00Z".parse::<Interval>(), Err(Error::InvalidTimestamp));
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Interval {
+    /// `start/end`
+    StartEnd {
+        /// Start of the interval.
+        start: TimeStampMs,
+        /// End of the interval.
+        end: TimeStampMs,
+    },
+    /// `start/duration`
+    StartDuration {
+        /// Start of the interval.
+        start: TimeStampMs,
+        /// Duration of the interval.
+        duration: IsoDuration,
+    },
+    /// `duration/end`
+    DurationEnd {
+        /// Duration of the interval.
+        duration: IsoDuration,
+        /// End of the interval.
+        end: TimeStampMs,
+    },
+}
+
+/// Subtract a duration from a timestamp.
+fn sub_duration(duration: &IsoDuration, ts: TimeStampMs) -> Result<TimeStampMs, Error> {
+    IsoDuration {
+        negative: !duration.negative,
+        ..*duration
+    }
+    .add_to(ts)
+}
+
+/// Format a timestamp in RFC 3339 format, with milliseconds only if needed.
+fn format_timestamp(ts: TimeStampMs) -> Result<String, Error> {
+    if ts.milliseconds == 0 {
+        format_iso8601_utc(ts.seconds)
+    } else {
+        format_iso8601_ms_utc(ts)
+    }
+}
+
+impl Interval {
+    /// Start of the interval.
+    pub fn start(&self) -> Result<TimeStampMs, Error> {
+        match self {
+            Interval::StartEnd { start, .. } | Interval::StartDuration { start, .. } => Ok(*start),
+            Interval::DurationEnd { duration, end } => sub_duration(duration, *end),
+        }
+    }
+
+    /// End of the interval.
+    pub fn end(&self) -> Result<TimeStampMs, Error> {
+        match self {
+            Interval::StartEnd { end, .. } | Interval::DurationEnd { end, .. } => Ok(*end),
+            Interval::StartDuration { start, duration } => duration.add_to(*start),
+        }
+    }
+
+    /// Format the interval with the end abbreviated, omitting the date if it
+    /// is the same day as the start: `2025-05-20T10:00:00Z/12:00:00`.
+    ///
+    /// Only `start/end` intervals are abbreviated; other forms are formatted
+    /// as with `Display`.
+    pub fn format_abbreviated(&self) -> Result<String, Error> {
+        let (start, end) = match self {
+            Interval::StartEnd { start, end } => (*start, *end),
+            _ => return Ok(self.to_string()),
+        };
+        let start_str = format_timestamp(start)?;
+        let same_day = start.seconds.div_euclid(86_400) == end.seconds.div_euclid(86_400);
+        let end_str = match (same_day, end.milliseconds) {
+            (true, 0) => strftime_ms_utc("%H:%M:%S", end)?,
+            (true, _) => strftime_ms_utc("%H:%M:%S.{ms}", end)?,
+            (false, _) => format_timestamp(end)?,
+        };
+        Ok(format!("{}/{}", start_str, end_str))
+    }
+
+    /// The `n`th repetition of this interval, the interval itself being the 0th.
+    fn shifted(&self, n: u32) -> Result<(TimeStampMs, TimeStampMs), Error> {
+        let next = n.checked_add(1).ok_or(Error::InvalidTimestamp)?;
+        match self {
+            Interval::StartEnd { start, end } => {
+                let len = i128::from(end.total_milliseconds() - start.total_milliseconds());
+                let shift = |k: u32| -> Result<TimeStampMs, Error> {
+                    let ms = i128::from(start.total_milliseconds()) + len * i128::from(k);
+                    let seconds =
+                        i64::try_from(ms.div_euclid(1000)).map_err(|_| Error::InvalidTimestamp)?;
+                    Ok(TimeStampMs::new(seconds, ms.rem_euclid(1000) as u16))
+                };
+                Ok((shift(n)?, shift(next)?))
+            }
+            Interval::StartDuration { start, duration } => {
+                let scaled = |k: u32| duration.scaled(k).ok_or(Error::InvalidTimestamp);
+                Ok((scaled(n)?.add_to(*start)?, scaled(next)?.add_to(*start)?))
+            }
+            Interval::DurationEnd { duration, end } => {
+                let scaled = |k: u32| duration.scaled(k).ok_or(Error::InvalidTimestamp);
+                Ok((
+                    sub_duration(&scaled(next)?, *end)?,
+                    sub_duration(&scaled(n)?, *end)?,
+                ))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ts = |ts| format_timestamp(ts).map_err(|_| fmt::Error);
+        match self {
+            Interval::StartEnd { start, end } => write!(f, "{}/{}", ts(*start)?, ts(*end)?),
+            Interval::StartDuration { start, duration } => {
+                write!(f, "{}/{}", ts(*start)?, duration)
+            }
+            Interval::DurationEnd { duration, end } => write!(f, "{}/{}", duration, ts(*end)?),
+        }
+    }
+}
+
+impl FromStr for Interval {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, second) = s.split_once('/').ok_or(Error::ParseError)?;
+        let is_duration = |s: &str| s.starts_with('P') || s.starts_with("-P");
+        // The duration of an interval is its length, which can't be negative
+        let parse_duration = |s: &str| match s.parse::<IsoDuration>()? {
+            duration if duration.negative && !duration.is_zero() => Err(Error::InvalidTimestamp),
+            duration => Ok(duration),
+        };
+        match (is_duration(first), is_duration(second)) {
+            (true, true) => Err(Error::ParseError),
+            (true, false) => Ok(Interval::DurationEnd {
+                duration: parse_duration(first)?,
+                end: parse::parse_datetime(second)?.to_timestamp()?,
+            }),
+            (false, true) => Ok(Interval::StartDuration {
+                start: parse::parse_datetime(first)?.to_timestamp()?,
+                duration: parse_duration(second)?,
+            }),
+            (false, false) => {
+                let start = parse::parse_datetime(first)?;
+                let end = parse::parse_abbreviated_datetime(second, &start)?;
+                let (start, end) = (start.to_timestamp()?, end.to_timestamp()?);
+                if end.total_milliseconds() < start.total_milliseconds() {
+                    return Err(Error::InvalidTimestamp);
+                }
+                Ok(Interval::StartEnd { start, end })
+            }
+        }
+    }
+}
+
+/// An ISO 8601 recurring interval: `Rn/interval`, or `R/interval` for an
+/// unbounded number of recurrences.
+///
+/// ```rust
+/// use time_format::{RecurringInterval, TimeStampMs};
+///
+/// let r: RecurringInterval = "R3/2025-01-31T00:00:00Z/P1M".parse().unwrap();
+/// let starts: Vec<String> = r
+///     .iter()
+///     .map(|(start, _end)| time_format::format_iso8601_utc(start.seconds).unwrap())
+///     .collect();
+/// assert_eq!(
+///     starts,
+///     ["2025-01-31T00:00:00Z", "2025-02-28T00:00:00Z", "2025-03-31T00:00:00Z"]
+/// );
+/// assert_eq!(r.to_string(), "R3/2025-01-31T00:00:00Z/P1M");
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RecurringInterval {
+    /// Number of recurrences, or `None` if unbounded.
+    pub repetitions: Option<u32>,
+    /// The first interval (or the last one, for the `duration/end` form).
+    pub interval: Interval,
+}
+
+impl RecurringInterval {
+    /// Iterate over the `(start, end)` pairs of the recurrences.
+    ///
+    /// Each recurrence is computed from the original interval rather than
+    /// from the previous one, so that adding `P1M` to January 31st yields
+    /// February 28th, then March 31st. Recurrences of the `duration/end`
+    /// form end at the given end time: they are returned in chronological
+    /// order if the number of recurrences is bounded, and in reverse
+    /// chronological order otherwise.
+    pub fn iter(&self) -> Recurrences {
+        Recurrences {
+            recurring: *self,
+            index: 0,
+        }
+    }
+}
+
+impl fmt::Display for RecurringInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.repetitions {
+            Some(n) => write!(f, "R{}/{}", n, self.interval),
+            None => write!(f, "R/{}", self.interval),
+        }
+    }
+}
+
+impl FromStr for RecurringInterval {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s.strip_prefix('R').ok_or(Error::ParseError)?;
+        let (count, interval) = rest.split_once('/').ok_or(Error::ParseError)?;
+        let repetitions = match count {
+            "" | "-1" => None,
+            count if count.bytes().all(|b| b.is_ascii_digit()) => {
+                Some(count.parse().map_err(|_| Error::ParseError)?)
+            }
+            _ => return Err(Error::ParseError),
+        };
+        Ok(RecurringInterval {
+            repetitions,
+            interval: interval.parse()?,
+        })
+    }
+}
+
+impl IntoIterator for &RecurringInterval {
+    type Item = (TimeStampMs, TimeStampMs);
+    type IntoIter = Recurrences;
+
+    fn into_iter(self) -> Recurrences {
+        self.iter()
+    }
+}
+
+/// Iterator over the recurrences of a [`RecurringInterval`].
+///
+/// The iteration stops early if a recurrence falls outside of the range of
+/// representable timestamps.
+#[derive(Debug, Clone)]
+pub struct Recurrences {
+    recurring: RecurringInterval,
+    index: u32,
+}
+
+impl Iterator for Recurrences {
+    type Item = (TimeStampMs, TimeStampMs);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let n = match self.recurring.repetitions {
+            Some(count) if self.index >= count => return None,
+            // Bounded `duration/end` recurrences are returned oldest first
+            Some(count) if matches!(self.recurring.interval, Interval::DurationEnd { .. }) => {
+                count - 1 - self.index
+            }
+            _ => self.index,
+        };
+        let item = self.recurring.interval.shifted(n).ok()?;
+        self.index = self.index.checked_add(1)?;
+        Some(item)
+    }
+}
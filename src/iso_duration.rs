@@ -0,0 +1,271 @@
+//! ISO 8601 durations, such as `P1Y2M10DT2H30M` or `PT4.5S`.
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use crate::{calendar, Error, TimeStampMs};
+
+/// An ISO 8601 duration (`PnYnMnWnDTnHnMnS`).
+///
+/// Years, months, weeks and days are nominal: their length depends on the
+/// date they are added to. Hours, minutes and seconds are exact.
+///
+/// ```rust
+/// use time_format::{IsoDuration, TimeStampMs};
+///
+/// let d: IsoDuration = "P1Y2M10DT2H30M4.5S".parse().unwrap();
+/// assert_eq!((d.years, d.months, d.days), (1, 2, 10));
+/// assert_eq!((d.hours, d.minutes, d.seconds, d.nanoseconds), (2, 30, 4, 500_000_000));
+/// assert_eq!(d.to_string(), "P1Y2M10DT2H30M4.5S");
+///
+/// // Adding a month to January 31st stops at the end of February
+/// let jan31 = TimeStampMs::from_timestamp(1738281600); // 2025-01-31T00:00:00Z
+/// let p1m: IsoDuration = "P1M".parse().unwrap();
+/// let feb28 = p1m.add_to(jan31).unwrap();
+/// assert_eq!(time_format::format_iso8601_utc(feb28.seconds).unwrap(), "2025-02-28T00:00:00Z");
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct IsoDuration {
+    /// Whether the duration is negative (`-P1D`).
+    pub negative: bool,
+    /// Nominal years.
+    pub years: u32,
+    /// Nominal months.
+    pub months: u32,
+    /// Nominal weeks.
+    pub weeks: u32,
+    /// Nominal days.
+    pub days: u32,
+    /// Hours.
+    pub hours: u32,
+    /// Minutes.
+    pub minutes: u32,
+    /// Seconds.
+    pub seconds: u32,
+    /// Fractional part of the seconds, in nanoseconds (0-999999999).
+    pub nanoseconds: u32,
+}
+
+impl IsoDuration {
+    /// Whether all the components are zero.
+    pub fn is_zero(&self) -> bool {
+        self.years == 0
+            && self.months == 0
+            && self.weeks == 0
+            && self.days == 0
+            && self.hours == 0
+            && self.minutes == 0
+            && self.seconds == 0
+            && self.nanoseconds == 0
+    }
+
+    /// This duration with every component multiplied by `n`, or `None` on overflow.
+    pub(crate) fn scaled(&self, n: u32) -> Option<IsoDuration> {
+        let nanoseconds = u64::from(self.nanoseconds) * u64::from(n);
+        let carry = u32::try_from(nanoseconds / 1_000_000_000).ok()?;
+        Some(IsoDuration {
+            negative: self.negative,
+            years: self.years.checked_mul(n)?,
+            months: self.months.checked_mul(n)?,
+            weeks: self.weeks.checked_mul(n)?,
+            days: self.days.checked_mul(n)?,
+            hours: self.hours.checked_mul(n)?,
+            minutes: self.minutes.checked_mul(n)?,
+            seconds: self.seconds.checked_mul(n)?.checked_add(carry)?,
+            nanoseconds: (nanoseconds % 1_000_000_000) as u32,
+        })
+    }
+
+    /// Add this duration to a timestamp, using the calendar in UTC.
+    ///
+    /// Years and months are added first; if the resulting day doesn't exist
+    /// in the target month, the last day of that month is used. Weeks and
+    /// days are then added as calendar days, followed by the exact part.
+    /// Sub-millisecond precision is truncated.
+    pub fn add_to(&self, ts: TimeStampMs) -> Result<TimeStampMs, Error> {
+        let sign: i64 = if self.negative { -1 } else { 1 };
+        let days = ts.seconds.div_euclid(calendar::SECS_PER_DAY);
+        let secs_of_day = ts.seconds.rem_euclid(calendar::SECS_PER_DAY);
+        let (year, month, day) = calendar::civil_from_days(days);
+
+        let months = i64::from(self.years) * 12 + i64::from(self.months);
+        let total_months = year
+            .checked_mul(12)
+            .and_then(|m| m.checked_add(i64::from(month) - 1 + sign * months))
+            .ok_or(Error::InvalidTimestamp)?;
+        let (year, month) = (
+            total_months.div_euclid(12),
+            total_months.rem_euclid(12) as u32 + 1,
+        );
+        let day = day.min(calendar::days_in_month(year, month));
+        let days = calendar::days_from_civil(year, month, day)
+            + sign * (i64::from(self.weeks) * 7 + i64::from(self.days));
+
+        let exact_ms =
+            (i64::from(self.hours) * 3600 + i64::from(self.minutes) * 60 + i64::from(self.seconds))
+                * 1000
+                + i64::from(self.nanoseconds / 1_000_000);
+        let total_ms = i128::from(days) * i128::from(calendar::SECS_PER_DAY) * 1000
+            + i128::from(secs_of_day) * 1000
+            + i128::from(ts.milliseconds)
+            + i128::from(sign * exact_ms);
+        let seconds =
+            i64::try_from(total_ms.div_euclid(1000)).map_err(|_| Error::InvalidTimestamp)?;
+        Ok(TimeStampMs::new(seconds, total_ms.rem_euclid(1000) as u16))
+    }
+}
+
+impl fmt::Display for IsoDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative && !self.is_zero() {
+            f.write_str("-")?;
+        }
+        f.write_str("P")?;
+        for (value, designator) in [
+            (self.years, 'Y'),
+            (self.months, 'M'),
+            (self.weeks, 'W'),
+            (self.days, 'D'),
+        ] {
+            if value > 0 {
+                write!(f, "{}{}", value, designator)?;
+            }
+        }
+        let has_time =
+            self.hours > 0 || self.minutes > 0 || self.seconds > 0 || self.nanoseconds > 0;
+        if !has_time {
+            if self.is_zero() {
+                f.write_str("T0S")?;
+            }
+            return Ok(());
+        }
+        f.write_str("T")?;
+        if self.hours > 0 {
+            write!(f, "{}H", self.hours)?;
+        }
+        if self.minutes > 0 {
+            write!(f, "{}M", self.minutes)?;
+        }
+        if self.seconds > 0 || self.nanoseconds > 0 {
+            write!(f, "{}", self.seconds)?;
+            if self.nanoseconds > 0 {
+                let frac = format!("{:09}", self.nanoseconds);
+                write!(f, ".{}", frac.trim_end_matches('0'))?;
+            }
+            f.write_str("S")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for IsoDuration {
+    type Err = Error;
+
+    /// Parse an ISO 8601 duration such as `P3W`, `P1Y2M10DT2H30M` or `PT4.5S`.
+    ///
+    /// Components must appear in order, and only the seconds may have a
+    /// fractional part, with either `.` or `,` as the decimal separator.
+    /// A leading `-` denotes a negative duration.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut duration = IsoDuration::default();
+        let mut rest = s;
+        if let Some(after) = rest.strip_prefix('-') {
+            duration.negative = true;
+            rest = after;
+        }
+        rest = rest.strip_prefix('P').ok_or(Error::ParseError)?;
+        let (date_part, time_part) = match rest.find('T') {
+            Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            None => (rest, None),
+        };
+
+        let mut any = false;
+        let mut order = 0;
+        let mut date_part = date_part;
+        while !date_part.is_empty() {
+            let (value, frac, designator, after) = parse_component(date_part)?;
+            let (slot, pos) = match designator {
+                'Y' => (&mut duration.years, 1),
+                'M' => (&mut duration.months, 2),
+                'W' => (&mut duration.weeks, 3),
+                'D' => (&mut duration.days, 4),
+                _ => return Err(Error::ParseError),
+            };
+            if pos <= order || frac.is_some() {
+                return Err(Error::ParseError);
+            }
+            *slot = value;
+            order = pos;
+            any = true;
+            date_part = after;
+        }
+
+        if let Some(mut time_part) = time_part {
+            if time_part.is_empty() {
+                return Err(Error::ParseError);
+            }
+            let mut order = 0;
+            while !time_part.is_empty() {
+                let (value, frac, designator, after) = parse_component(time_part)?;
+                let pos = match designator {
+                    'H' => 1,
+                    'M' => 2,
+                    'S' => 3,
+                    _ => return Err(Error::ParseError),
+                };
+                if pos <= order || (frac.is_some() && designator != 'S') {
+                    return Err(Error::ParseError);
+                }
+                match designator {
+                    'H' => duration.hours = value,
+                    'M' => duration.minutes = value,
+                    _ => {
+                        duration.seconds = value;
+                        duration.nanoseconds = frac.unwrap_or(0);
+                    }
+                }
+                order = pos;
+                any = true;
+                time_part = after;
+            }
+        }
+
+        if !any {
+            return Err(Error::ParseError);
+        }
+        Ok(duration)
+    }
+}
+
+/// Parse a number with an optional fraction, followed by a designator.
+///
+/// Returns the integer part, the fraction in nanoseconds, the designator
+/// and the remaining input.
+fn parse_component(s: &str) -> Result<(u32, Option<u32>, char, &str), Error> {
+    let int_len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if int_len == 0 {
+        return Err(Error::ParseError);
+    }
+    let value = s[..int_len].parse().map_err(|_| Error::ParseError)?;
+    let mut rest = &s[int_len..];
+
+    let mut frac = None;
+    if let Some(after) = rest.strip_prefix(['.', ',']) {
+        let len = after
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after.len());
+        if len == 0 {
+            return Err(Error::ParseError);
+        }
+        let mut nanos = 0u32;
+        let mut scale = 100_000_000;
+        for digit in after[..len].bytes() {
+            nanos += u32::from(digit - b'0') * scale;
+            scale /= 10;
+        }
+        frac = Some(nanos);
+        rest = &after[len..];
+    }
+
+    let designator = rest.chars().next().ok_or(Error::ParseError)?;
+    Ok((value, frac, designator, &rest[designator.len_utf8()..]))
+}
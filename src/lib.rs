@@ -1,11 +1,50 @@
 #![doc = include_str!("../README.md")]
 
 use std::{
-    convert::TryInto,
+    convert::{TryFrom, TryInto},
     ffi::CString,
     fmt,
     mem::MaybeUninit,
     os::raw::{c_char, c_int, c_long},
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+#[cfg(any(feature = "log", feature = "tracing-subscriber"))]
+mod cache;
+mod calendar;
+mod display;
+mod duration;
+mod epoch;
+mod infer;
+mod interval;
+mod iso_duration;
+mod locale;
+#[cfg(feature = "log")]
+pub mod log;
+mod parse;
+mod pattern;
+mod relative;
+pub mod rewrite;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod strftime;
+#[cfg(feature = "tracing-subscriber")]
+pub mod tracing;
+
+pub use display::{DisplayDate, DisplayTime};
+pub use duration::{
+    format_duration, format_duration_ms, parse_duration, parse_duration_ms, DurationStyle,
+};
+pub use epoch::{EpochDetector, EpochUnit};
+pub use infer::{infer_format, FormatCandidate};
+pub use interval::{Interval, Recurrences, RecurringInterval};
+pub use iso_duration::IsoDuration;
+pub use locale::{Era, Locale};
+pub use pattern::{PatternLanguage, Translation, Untranslatable};
+pub use relative::{
+    format_relative, format_relative_now, RelativeFormatter, RelativeLocale, RelativeThresholds,
+    RelativeTime, RelativeUnit,
 };
 
 #[allow(non_camel_case_types)]
@@ -47,6 +86,204 @@ impl TimeStampMs {
     }
 }
 
+impl FromStr for TimeStampMs {
+    type Err = Error;
+
+    /// Parse an RFC 3339 date and time, an integer number of seconds since
+    /// the UNIX epoch, or a number of seconds with a fractional part.
+    ///
+    /// Fractions of a second are truncated to milliseconds.
+    ///
+    /// ```rust
+    /// use time_format::TimeStampMs;
+    ///
+    /// let ts: TimeStampMs = "2025-05-20T16:30:45.123+02:00".parse().unwrap();
+    /// assert_eq!(ts, TimeStampMs::new(1747751445, 123));
+    ///
+    /// assert_eq!("1747751445".parse::<TimeStampMs>().unwrap(), TimeStampMs::new(1747751445, 0));
+    /// assert_eq!("1747751445.123456".parse::<TimeStampMs>().unwrap(), TimeStampMs::new(1747751445, 123));
+    /// assert_eq!("-1.5".parse::<TimeStampMs>().unwrap(), TimeStampMs::new(-2, 500));
+    /// assert!("tomorrow".parse::<TimeStampMs>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+        let is_number = !int_part.is_empty()
+            && int_part.bytes().all(|b| b.is_ascii_digit())
+            && frac_part.bytes().all(|b| b.is_ascii_digit())
+            && !digits.ends_with('.');
+        if !is_number {
+            return parse::parse_rfc3339(s);
+        }
+
+        let seconds: i64 = int_part.parse().map_err(|_| Error::InvalidTimestamp)?;
+        let millis = frac_part
+            .bytes()
+            .chain(std::iter::repeat(b'0'))
+            .take(3)
+            .fold(0, |acc, digit| acc * 10 + i64::from(digit - b'0'));
+        let total_ms = seconds
+            .checked_mul(1000)
+            .and_then(|ms| ms.checked_add(millis))
+            .ok_or(Error::InvalidTimestamp)?;
+        let total_ms = if negative { -total_ms } else { total_ms };
+        Ok(TimeStampMs::new(
+            total_ms.div_euclid(1000),
+            total_ms.rem_euclid(1000) as u16,
+        ))
+    }
+}
+
+impl TryFrom<SystemTime> for TimeStampMs {
+    type Error = Error;
+
+    /// Convert a `SystemTime`, including times before the UNIX epoch.
+    ///
+    /// Fractions of a millisecond are truncated towards the past.
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => TimeStampMs::try_from(since),
+            Err(before) => {
+                let before = before.duration();
+                let nanos =
+                    i128::try_from(before.as_nanos()).map_err(|_| Error::InvalidTimestamp)?;
+                let total_ms = (-nanos).div_euclid(1_000_000);
+                let seconds = i64::try_from(total_ms.div_euclid(1000))
+                    .map_err(|_| Error::InvalidTimestamp)?;
+                Ok(TimeStampMs::new(seconds, total_ms.rem_euclid(1000) as u16))
+            }
+        }
+    }
+}
+
+impl From<TimeStampMs> for SystemTime {
+    /// Convert to a `SystemTime`.
+    ///
+    /// Panics if the timestamp can't be represented by `SystemTime` on this
+    /// platform.
+    ///
+    /// ```rust
+    /// use std::{convert::TryFrom, time::SystemTime};
+    /// use time_format::TimeStampMs;
+    ///
+    /// let ts = TimeStampMs::new(-1, 250);
+    /// let time = SystemTime::from(ts);
+    /// assert_eq!(TimeStampMs::try_from(time).unwrap(), ts);
+    /// ```
+    fn from(ts: TimeStampMs) -> Self {
+        let since_epoch = Duration::from_millis(u64::from(ts.milliseconds));
+        if ts.seconds >= 0 {
+            UNIX_EPOCH + Duration::from_secs(ts.seconds as u64) + since_epoch
+        } else {
+            UNIX_EPOCH - Duration::from_secs(ts.seconds.unsigned_abs()) + since_epoch
+        }
+    }
+}
+
+impl TryFrom<Duration> for TimeStampMs {
+    type Error = Error;
+
+    /// Convert a duration since the UNIX epoch.
+    ///
+    /// Fractions of a millisecond are truncated.
+    ///
+    /// ```rust
+    /// use std::{convert::TryFrom, time::Duration};
+    /// use time_format::TimeStampMs;
+    ///
+    /// let ts = TimeStampMs::try_from(Duration::from_millis(1747751445123)).unwrap();
+    /// assert_eq!(ts, TimeStampMs::new(1747751445, 123));
+    /// assert!(TimeStampMs::try_from(Duration::MAX).is_err());
+    /// ```
+    fn try_from(since_epoch: Duration) -> Result<Self, Self::Error> {
+        let seconds = i64::try_from(since_epoch.as_secs()).map_err(|_| Error::InvalidTimestamp)?;
+        Ok(TimeStampMs::new(
+            seconds,
+            since_epoch.subsec_millis() as u16,
+        ))
+    }
+}
+
+/// A UNIX timestamp with microsecond precision.
+///
+/// ```rust
+/// use time_format::{TimeStampMs, TimeStampUs};
+///
+/// let ts_us = TimeStampUs::new(1747751445, 123456);
+/// assert_eq!(ts_us.to_timestamp_ms(), TimeStampMs::new(1747751445, 123));
+/// assert_eq!(TimeStampUs::from(TimeStampMs::new(1747751445, 123)), TimeStampUs::new(1747751445, 123000));
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TimeStampUs {
+    /// Seconds since the UNIX epoch.
+    pub seconds: i64,
+    /// Microseconds component (0-999999).
+    pub microseconds: u32,
+}
+
+impl TimeStampUs {
+    /// Create a new TimeStampUs from seconds and microseconds.
+    pub fn new(seconds: i64, microseconds: u32) -> Self {
+        let microseconds = microseconds % 1_000_000;
+        Self {
+            seconds,
+            microseconds,
+        }
+    }
+
+    /// Convert from a TimeStamp (seconds only).
+    pub fn from_timestamp(ts: TimeStamp) -> Self {
+        Self {
+            seconds: ts,
+            microseconds: 0,
+        }
+    }
+
+    /// Truncate to millisecond precision.
+    pub fn to_timestamp_ms(self) -> TimeStampMs {
+        TimeStampMs::new(self.seconds, (self.microseconds / 1000) as u16)
+    }
+}
+
+impl From<TimeStampMs> for TimeStampUs {
+    fn from(ts_ms: TimeStampMs) -> Self {
+        Self::new(ts_ms.seconds, u32::from(ts_ms.milliseconds) * 1000)
+    }
+}
+
+impl TryFrom<SystemTime> for TimeStampUs {
+    type Error = Error;
+
+    /// Convert a `SystemTime`, including times before the UNIX epoch.
+    ///
+    /// Fractions of a microsecond are truncated towards the past.
+    ///
+    /// ```rust
+    /// use std::{convert::TryFrom, time::{Duration, UNIX_EPOCH}};
+    /// use time_format::TimeStampUs;
+    ///
+    /// let time = UNIX_EPOCH + Duration::from_nanos(1747751445123456789);
+    /// assert_eq!(TimeStampUs::try_from(time).unwrap(), TimeStampUs::new(1747751445, 123456));
+    /// ```
+    fn try_from(time: SystemTime) -> Result<Self, Self::Error> {
+        let nanos = match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => i128::try_from(since.as_nanos()),
+            Err(before) => i128::try_from(before.duration().as_nanos()).map(|nanos| -nanos),
+        }
+        .map_err(|_| Error::InvalidTimestamp)?;
+        let total_us = nanos.div_euclid(1000);
+        let seconds =
+            i64::try_from(total_us.div_euclid(1_000_000)).map_err(|_| Error::InvalidTimestamp)?;
+        Ok(TimeStampUs::new(
+            seconds,
+            total_us.rem_euclid(1_000_000) as u32,
+        ))
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 struct tm {
@@ -71,6 +308,7 @@ extern "C" {
 }
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     /// Error occurred while parsing or converting time
     TimeError,
@@ -78,12 +316,18 @@ pub enum Error {
     InvalidTimestamp,
     /// Error occurred while formatting time
     FormatError,
-    /// Error with format string (e.g., invalid format specifier)
+    /// Error with format string (e.g., rejected by the system strftime)
     InvalidFormatString,
     /// Error with UTF-8 conversion from C string
     Utf8Error,
     /// Error with null bytes in input strings
     NullByteError,
+    /// Format string rejected by validation, with the location and cause
+    InvalidFormat(FormatStringError),
+    /// Error parsing a string into a time value
+    ParseError,
+    /// An integer timestamp could be in several units (see [`EpochDetector`])
+    AmbiguousTimestamp,
 }
 
 impl fmt::Display for Error {
@@ -95,70 +339,165 @@ impl fmt::Display for Error {
             Error::InvalidFormatString => write!(f, "Invalid format string"),
             Error::Utf8Error => write!(f, "UTF-8 conversion error"),
             Error::NullByteError => write!(f, "String contains null bytes"),
+            Error::InvalidFormat(e) => write!(f, "Invalid format string: {}", e),
+            Error::ParseError => write!(f, "Parse error"),
+            Error::AmbiguousTimestamp => write!(f, "Ambiguous timestamp unit"),
         }
     }
 }
 
 impl std::error::Error for Error {}
 
-/// Validates a strftime format string for correct syntax.
-/// This performs a basic validation to catch common errors.
-///
-/// Returns Ok(()) if the format appears valid, or an error describing the issue.
-pub fn validate_format(format: impl AsRef<str>) -> Result<(), Error> {
-    let format = format.as_ref();
-
-    // Check for empty format
-    if format.is_empty() {
-        return Err(Error::InvalidFormatString);
+impl From<FormatStringError> for Error {
+    fn from(e: FormatStringError) -> Self {
+        Error::InvalidFormat(e)
     }
+}
 
-    // Check for null bytes (which would cause CString creation to fail)
-    if format.contains('\0') {
-        return Err(Error::NullByteError);
+/// Reason why a format string was rejected by [`validate_format`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum FormatErrorReason {
+    /// The format string is empty
+    Empty,
+    /// `%` is followed by a character that is not a known conversion specifier
+    UnknownSpecifier,
+    /// The format string ends with an incomplete `%` directive
+    TrailingPercent,
+    /// A field width is larger than 255
+    WidthTooLarge,
+    /// A `:` modifier is used with a conversion other than `%z`, or more than twice
+    InvalidColonModifier,
+    /// The `E` or `O` modifier is followed by a conversion that doesn't accept it
+    InvalidModifier,
+    /// A `{` has no matching `}`, and isn't escaped as `{{`
+    UnmatchedOpeningBrace,
+    /// A `}` has no matching `{`, and isn't escaped as `}}`
+    UnmatchedClosingBrace,
+    /// A `{...}` placeholder other than `{ms}`
+    UnknownPlaceholder,
+    /// A date or time pattern of a [`Locale`] uses `%c`, `%x`, `%X` or `%r`,
+    /// which would expand to another pattern
+    NestedLocalePattern,
+}
+
+impl fmt::Display for FormatErrorReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatErrorReason::Empty => write!(f, "empty format string"),
+            FormatErrorReason::UnknownSpecifier => write!(f, "unknown conversion specifier"),
+            FormatErrorReason::TrailingPercent => write!(f, "trailing '%'"),
+            FormatErrorReason::WidthTooLarge => write!(f, "field width too large"),
+            FormatErrorReason::InvalidColonModifier => write!(f, "invalid ':' modifier"),
+            FormatErrorReason::InvalidModifier => write!(f, "invalid 'E' or 'O' modifier"),
+            FormatErrorReason::UnmatchedOpeningBrace => write!(f, "unmatched '{{'"),
+            FormatErrorReason::UnmatchedClosingBrace => write!(f, "unmatched '}}'"),
+            FormatErrorReason::UnknownPlaceholder => write!(f, "unknown placeholder"),
+            FormatErrorReason::NestedLocalePattern => write!(f, "nested locale pattern"),
+        }
     }
+}
 
-    let mut chars = format.chars().peekable();
-    while let Some(c) = chars.next() {
-        // Look for % sequences
-        if c == '%' {
-            match chars.next() {
-                // These are the most common format specifiers
-                Some('a') | Some('A') | Some('b') | Some('B') | Some('c') | Some('C')
-                | Some('d') | Some('D') | Some('e') | Some('F') | Some('g') | Some('G')
-                | Some('h') | Some('H') | Some('I') | Some('j') | Some('k') | Some('l')
-                | Some('m') | Some('M') | Some('n') | Some('p') | Some('P') | Some('r')
-                | Some('R') | Some('s') | Some('S') | Some('t') | Some('T') | Some('u')
-                | Some('U') | Some('V') | Some('w') | Some('W') | Some('x') | Some('X')
-                | Some('y') | Some('Y') | Some('z') | Some('Z') | Some('%') | Some('E')
-                | Some('O') | Some('+') => {
-                    // Valid format specifier
-                    continue;
-                }
-                Some(_c) => {
-                    // Unknown format specifier
-                    return Err(Error::InvalidFormatString);
-                }
-                None => {
-                    // % at end of string
-                    return Err(Error::InvalidFormatString);
-                }
-            }
+/// Details about an invalid format string.
+///
+/// ```rust
+/// use time_format::{validate_format, Error, FormatErrorReason};
+///
+/// match validate_format("%Y-%m-%Q") {
+///     Err(Error::InvalidFormat(e)) => {
+///         assert_eq!(e.position, 6);
+///         assert_eq!(e.directive, Some('Q'));
+///         assert_eq!(e.reason, FormatErrorReason::UnknownSpecifier);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub struct FormatStringError {
+    /// Byte offset of the offending `%` or brace in the format string.
+    pub position: usize,
+    /// The offending character: the conversion specifier following `%`,
+    /// or the unmatched brace. `None` if the string ended unexpectedly.
+    pub directive: Option<char>,
+    /// Why the format string was rejected.
+    pub reason: FormatErrorReason,
+}
+
+impl FormatStringError {
+    fn new(position: usize, directive: Option<char>, reason: FormatErrorReason) -> Self {
+        Self {
+            position,
+            directive,
+            reason,
         }
     }
+}
 
-    // Check for the special {ms} sequence format
-    let ms_braces = format.match_indices('{').count();
-    let ms_closing_braces = format.match_indices('}').count();
-    if ms_braces != ms_closing_braces {
-        return Err(Error::InvalidFormatString);
+impl fmt::Display for FormatStringError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.reason, self.directive) {
+            (FormatErrorReason::UnknownSpecifier, Some(c))
+            | (FormatErrorReason::InvalidColonModifier, Some(c))
+            | (FormatErrorReason::NestedLocalePattern, Some(c)) => {
+                write!(f, "{} '%{}' at byte {}", self.reason, c, self.position)
+            }
+            (FormatErrorReason::InvalidModifier, Some(c)) => {
+                write!(
+                    f,
+                    "{} before '{}' at byte {}",
+                    self.reason, c, self.position
+                )
+            }
+            _ => write!(f, "{} at byte {}", self.reason, self.position),
+        }
     }
+}
 
-    Ok(())
+/// Validates a strftime format string for correct syntax.
+/// This performs a basic validation to catch common errors.
+///
+/// In addition to the standard conversion specifiers, the GNU extensions are
+/// accepted: the `-` (no padding), `_` (space padding), `0` (zero padding),
+/// `^` (uppercase) and `#` (swap case) flags, a field width such as `%10Y`,
+/// and `%:z`/`%::z` for offsets with colons.
+///
+/// The `E` modifier is only accepted before `c`, `C`, `x`, `X`, `y` and `Y`,
+/// and the `O` modifier before `d`, `e`, `H`, `I`, `m`, `M`, `S`, `u`, `U`,
+/// `V`, `w`, `W` and `y`, as specified by POSIX. Their alternative era and
+/// numeral representations come from the process locale, or from the
+/// [`Locale`] passed to the `*_locale` functions; in locales that don't
+/// define any, they render like the unmodified conversion.
+///
+/// ```rust
+/// use time_format::{validate_format, Error, FormatErrorReason};
+///
+/// assert!(validate_format("%Ec %Od").is_ok());
+/// match validate_format("%EQ") {
+///     Err(Error::InvalidFormat(e)) => assert_eq!(e.reason, FormatErrorReason::InvalidModifier),
+///     _ => unreachable!(),
+/// }
+/// ```
+///
+/// Literal braces must be escaped as `{{` and `}}`, and `{ms}` is the only
+/// recognized placeholder:
+///
+/// ```rust
+/// assert!(time_format::validate_format("{{%H:%M:%S.{ms}}}").is_ok());
+/// assert!(time_format::validate_format("%H:%M:%S.{us}").is_err());
+/// assert!(time_format::validate_format("{%H}").is_err());
+/// ```
+///
+/// Returns Ok(()) if the format appears valid, or an error describing the issue.
+/// Syntax errors are reported as [`Error::InvalidFormat`], which carries the
+/// byte offset, the offending character and the reason.
+pub fn validate_format(format: impl AsRef<str>) -> Result<(), Error> {
+    check_format(format.as_ref()).map(|_| ())
 }
 
 /// Time components.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
 pub struct Components {
     /// Second.
     pub sec: u8,
@@ -404,11 +743,26 @@ pub fn now_ms() -> Result<TimeStampMs, Error> {
 /// The time is assumed to be the number of seconds since the Epoch.
 ///
 /// This function will validate the format string before attempting to format the time.
+///
+/// GNU flags and field widths are supported, and rendered by this crate
+/// when the system strftime doesn't implement them:
+///
+/// ```rust
+/// let ts = 1747701045; // 2025-05-20T00:30:45Z
+///
+/// assert_eq!(time_format::strftime_utc("%-m/%-d/%Y", ts).unwrap(), "5/20/2025");
+/// assert_eq!(time_format::strftime_utc("%_H:%M", ts).unwrap(), " 0:30");
+/// assert_eq!(time_format::strftime_utc("%^a %^b", ts).unwrap(), "TUE MAY");
+/// assert_eq!(time_format::strftime_utc("%10Y", ts).unwrap(), "0000002025");
+/// assert_eq!(time_format::strftime_utc("%H:%M%:z", ts).unwrap(), "00:30+00:00");
+/// ```
+///
+/// Literal braces are written `{{` and `}}`. The `{ms}` placeholder is
+/// accepted, and always renders as `000` since the timestamp has no
+/// sub-second part.
 pub fn strftime_utc(format: impl AsRef<str>, ts_seconds: TimeStamp) -> Result<String, Error> {
-    let format = format.as_ref();
-
     // Validate the format string
-    validate_format(format)?;
+    let format = check_format(format.as_ref())?;
 
     let mut tm = MaybeUninit::<tm>::uninit();
     if unsafe { gmtime_r(&ts_seconds, tm.as_mut_ptr()) }.is_null() {
@@ -416,7 +770,7 @@ pub fn strftime_utc(format: impl AsRef<str>, ts_seconds: TimeStamp) -> Result<St
     }
     let tm = unsafe { tm.assume_init() };
 
-    format_time_with_tm(format, &tm)
+    format_time_with_tm(format, &tm, Fraction::millis(0), None)
 }
 
 /// Return the current time in the specified format, in the local time zone.
@@ -424,10 +778,8 @@ pub fn strftime_utc(format: impl AsRef<str>, ts_seconds: TimeStamp) -> Result<St
 ///
 /// This function will validate the format string before attempting to format the time.
 pub fn strftime_local(format: impl AsRef<str>, ts_seconds: TimeStamp) -> Result<String, Error> {
-    let format = format.as_ref();
-
     // Validate the format string
-    validate_format(format)?;
+    let format = check_format(format.as_ref())?;
 
     let mut tm = MaybeUninit::<tm>::uninit();
     if unsafe { localtime_r(&ts_seconds, tm.as_mut_ptr()) }.is_null() {
@@ -435,11 +787,410 @@ pub fn strftime_local(format: impl AsRef<str>, ts_seconds: TimeStamp) -> Result<
     }
     let tm = unsafe { tm.assume_init() };
 
-    format_time_with_tm(format, &tm)
+    format_time_with_tm(format, &tm, Fraction::millis(0), None)
 }
 
 // Internal helper function to format time with a tm struct
-fn format_time_with_tm(format: &str, tm: &tm) -> Result<String, Error> {
+fn format_time_with_tm(
+    format: CheckedFormat<'_>,
+    tm: &tm,
+    fraction: Fraction,
+    locale: Option<&Locale>,
+) -> Result<String, Error> {
+    let mut out = String::new();
+    write_time_with_tm(&mut out, format, tm, fraction, locale)?;
+    Ok(out)
+}
+
+// Same as `format_time_with_tm`, writing into `out` instead of a new string
+//
+// Formats that the system strftime supports as is are passed to it
+// directly. Others are split into tokens on the fly, without allocating.
+fn write_time_with_tm<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    format: CheckedFormat<'_>,
+    tm: &tm,
+    fraction: Fraction,
+    locale: Option<&Locale>,
+) -> Result<(), Error> {
+    if format.plain && locale.is_none() {
+        return system_strftime_to(out, format.format, tm);
+    }
+    // The format was validated, so that tokenizing can't fail
+    let tokens = strftime::tokens(format.format).map_while(Result::ok);
+    write_tokens(out, tokens, tm, fraction, locale)
+}
+
+// Format tokens into `out`
+//
+// Placeholders are substituted here, so that text produced by strftime is
+// never interpreted as a placeholder. Directives using GNU flags, widths or
+// colons are also rendered here, so that the output doesn't depend on the
+// system strftime supporting them.
+fn write_tokens<'t, W: fmt::Write + ?Sized>(
+    out: &mut W,
+    tokens: impl IntoIterator<Item = strftime::Token<'t>>,
+    tm: &tm,
+    fraction: Fraction,
+    locale: Option<&Locale>,
+) -> Result<(), Error> {
+    let mut chunk = Chunk::new(out, tm);
+    for token in tokens {
+        let res = match token {
+            strftime::Token::Text(text) => chunk.push_text(text),
+            strftime::Token::Spec(spec) => {
+                let mut spec = spec;
+                let text =
+                    locale.and_then(|locale| locale.conversion(spec.modifier, spec.conv, tm));
+                if text.is_none() && locale.is_some() {
+                    // The locale has no era or numerals for this conversion
+                    spec.modifier = None;
+                }
+                if let Some(text) = text {
+                    write_flagged_with(&mut chunk, &spec, |out| {
+                        write_locale_text(out, &text, spec.conv, tm, fraction, locale)
+                    })
+                } else if spec.is_plain() {
+                    let mut buf = [0; 8];
+                    chunk.push_directive(strftime::plain_directive(&spec, &mut buf))
+                } else {
+                    write_flagged_with(&mut chunk, &spec, |out| {
+                        if spec.colons > 0 {
+                            strftime::write_offset(out, tm, spec.colons)
+                                .map_err(|_| Error::FormatError)
+                        } else {
+                            let mut buf = [0; 8];
+                            system_strftime_lossy(
+                                out,
+                                strftime::plain_directive(&spec, &mut buf),
+                                tm,
+                            )
+                        }
+                    })
+                }
+            }
+            strftime::Token::Millis => {
+                fmt::Write::write_fmt(&mut chunk, format_args!("{}", fraction))
+                    .map_err(|_| Error::FormatError)
+            }
+        };
+        res.map_err(|e| chunk.error.take().unwrap_or(e))?;
+    }
+    chunk.flush()
+}
+
+// Literal text and plain directives, formatted by a single strftime call
+// when the buffer is full or flushed
+//
+// Text written with `fmt::Write` is escaped, so that the output of
+// conversions rendered by this crate can be buffered as well.
+struct Chunk<'a, W: fmt::Write + ?Sized> {
+    out: &'a mut W,
+    tm: &'a tm,
+    pending: strftime::StackBuf,
+    // Why the last write failed, as `fmt::Write` can't tell
+    error: Option<Error>,
+}
+
+impl<'a, W: fmt::Write + ?Sized> Chunk<'a, W> {
+    fn new(out: &'a mut W, tm: &'a tm) -> Self {
+        Self {
+            out,
+            tm,
+            pending: strftime::StackBuf::new(),
+            error: None,
+        }
+    }
+
+    // Append a directive, or an escaped `%`
+    fn push_directive(&mut self, directive: &str) -> Result<(), Error> {
+        if fmt::Write::write_str(&mut self.pending, directive).is_err() {
+            self.flush()?;
+            fmt::Write::write_str(&mut self.pending, directive).map_err(|_| Error::FormatError)?;
+        }
+        Ok(())
+    }
+
+    // Append literal text
+    fn push_text(&mut self, text: &str) -> Result<(), Error> {
+        for (i, part) in text.split('%').enumerate() {
+            if i > 0 {
+                self.push_directive("%%")?;
+            }
+            if fmt::Write::write_str(&mut self.pending, part).is_ok() {
+                continue;
+            }
+            self.flush()?;
+            if fmt::Write::write_str(&mut self.pending, part).is_err() {
+                // Text longer than the buffer doesn't need strftime at all
+                write_str(self.out, part)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Write the rendering of the pending text and directives
+    fn flush(&mut self) -> Result<(), Error> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        system_strftime_to(self.out, self.pending.as_str(), self.tm)?;
+        self.pending.clear();
+        Ok(())
+    }
+}
+
+impl<W: fmt::Write + ?Sized> fmt::Write for Chunk<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_text(s).map_err(|e| {
+            self.error = Some(e);
+            fmt::Error
+        })
+    }
+}
+
+// Write the rendering of a locale-dependent conversion
+fn write_locale_text(
+    out: &mut dyn fmt::Write,
+    text: &locale::LocaleText,
+    conv: char,
+    tm: &tm,
+    fraction: Fraction,
+    locale: Option<&Locale>,
+) -> Result<(), Error> {
+    let res = match *text {
+        locale::LocaleText::Name(name) if conv == 'P' => name
+            .chars()
+            .flat_map(char::to_lowercase)
+            .try_for_each(|c| out.write_char(c)),
+        locale::LocaleText::Name(name) => out.write_str(name),
+        locale::LocaleText::Number(number) => write!(out, "{}", number),
+        locale::LocaleText::EraYear(era, year) => match era.first_year {
+            Some(first_year) if year == 1 => {
+                write!(out, "{}{}{}", era.name, first_year, era.year_suffix)
+            }
+            _ => write!(out, "{}{}{}", era.name, year, era.year_suffix),
+        },
+        locale::LocaleText::Pattern(pattern) => {
+            let pattern = check_locale_pattern(pattern)?;
+            return write_time_with_tm(out, pattern, tm, fraction, locale);
+        }
+    };
+    res.map_err(|_| Error::FormatError)
+}
+
+// Write a directive with its flags and width applied to what `render` writes
+//
+// The rendering goes through a stack buffer, or a string if it doesn't fit,
+// unless the directive has no flags and can be written directly.
+fn write_flagged_with<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    spec: &strftime::Spec,
+    render: impl Fn(&mut dyn fmt::Write) -> Result<(), Error>,
+) -> Result<(), Error> {
+    if spec.is_plain() {
+        return render(&mut WriteAdapter(out));
+    }
+    let mut buf = strftime::StackBuf::new();
+    let res = match render(&mut buf) {
+        Ok(()) => strftime::write_flagged(out, spec, buf.as_str()),
+        // The buffer was too small
+        Err(Error::FormatError) => {
+            let mut base = String::new();
+            render(&mut base)?;
+            strftime::write_flagged(out, spec, &base)
+        }
+        Err(e) => return Err(e),
+    };
+    res.map_err(|_| Error::FormatError)
+}
+
+// A `fmt::Write` implementation forwarding to another one, which may be unsized
+struct WriteAdapter<'a, W: fmt::Write + ?Sized>(&'a mut W);
+
+impl<W: fmt::Write + ?Sized> fmt::Write for WriteAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.write_str(s)
+    }
+}
+
+fn write_str<W: fmt::Write + ?Sized>(out: &mut W, s: &str) -> Result<(), Error> {
+    out.write_str(s).map_err(|_| Error::FormatError)
+}
+
+// Broken-down time for a timestamp in a time zone
+fn tm_in_zone(ts_seconds: TimeStamp, zone: TimeZone) -> Result<tm, Error> {
+    // Zone name of fixed offsets, which have none
+    static NO_ZONE_NAME: [c_char; 1] = [0];
+
+    let mut tm = MaybeUninit::<tm>::uninit();
+    let res = match zone {
+        TimeZone::Utc => unsafe { gmtime_r(&ts_seconds, tm.as_mut_ptr()) },
+        TimeZone::Local => unsafe { localtime_r(&ts_seconds, tm.as_mut_ptr()) },
+        TimeZone::FixedOffset(offset) => {
+            if offset.unsigned_abs() >= 86_400 {
+                return Err(Error::InvalidTimestamp);
+            }
+            let shifted = ts_seconds
+                .checked_add(i64::from(offset))
+                .ok_or(Error::InvalidTimestamp)?;
+            unsafe { gmtime_r(&shifted, tm.as_mut_ptr()) }
+        }
+    };
+    if res.is_null() {
+        return Err(Error::TimeError);
+    }
+    let mut tm = unsafe { tm.assume_init() };
+    if let TimeZone::FixedOffset(offset) = zone {
+        tm.tm_gmtoff = c_long::from(offset);
+        tm.tm_zone = NO_ZONE_NAME.as_ptr() as *mut c_char;
+    }
+    Ok(tm)
+}
+
+// Format a timestamp directly into a writer, as the `strftime_*` functions do
+pub(crate) fn write_strftime<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    format: &str,
+    ts_seconds: TimeStamp,
+    fraction: Fraction,
+    zone: TimeZone,
+    locale: Option<&Locale>,
+) -> Result<(), Error> {
+    let format = check_format(format)?;
+    let tm = tm_in_zone(ts_seconds, zone)?;
+    write_time_with_tm(out, format, &tm, fraction, locale)
+}
+
+// Fractional seconds substituted for `{ms}`, with leading zeros
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct Fraction {
+    value: u32,
+    digits: usize,
+}
+
+impl Fraction {
+    pub(crate) fn millis(milliseconds: u16) -> Self {
+        Self {
+            value: u32::from(milliseconds),
+            digits: 3,
+        }
+    }
+
+    pub(crate) fn micros(microseconds: u32) -> Self {
+        Self {
+            value: microseconds,
+            digits: 6,
+        }
+    }
+}
+
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:0width$}", self.value, width = self.digits)
+    }
+}
+
+// A validated format string
+#[derive(Clone, Copy)]
+struct CheckedFormat<'a> {
+    format: &'a str,
+    // Whether the system strftime can format it as is: no `{ms}`, brace
+    // escapes, flags, widths or colons
+    plain: bool,
+}
+
+// Validate a format string, without allocating
+fn check_format(format: &str) -> Result<CheckedFormat<'_>, Error> {
+    // Check for empty format
+    if format.is_empty() {
+        return Err(FormatStringError::new(0, None, FormatErrorReason::Empty).into());
+    }
+
+    // Check for null bytes (which would cause CString creation to fail)
+    if format.contains('\0') {
+        return Err(Error::NullByteError);
+    }
+
+    // Check the `%` directives, including GNU flags and widths, and the
+    // `{ms}` placeholder and brace escapes
+    let mut plain = true;
+    for token in strftime::tokens(format) {
+        plain &= match token? {
+            strftime::Token::Text(text) => !text.contains(['{', '}']),
+            strftime::Token::Spec(spec) => spec.is_plain(),
+            strftime::Token::Millis => false,
+        };
+    }
+    Ok(CheckedFormat { format, plain })
+}
+
+// Validate a date or time pattern of a locale
+//
+// Patterns are expanded one level only: they can't refer to other patterns,
+// which could expand to each other forever.
+fn check_locale_pattern(pattern: &str) -> Result<CheckedFormat<'_>, Error> {
+    let format = check_format(pattern)?;
+    for token in strftime::tokens(pattern).map_while(Result::ok) {
+        if let strftime::Token::Spec(spec) = token {
+            if matches!(spec.conv, 'c' | 'x' | 'X' | 'r') {
+                return Err(FormatStringError::new(
+                    spec.start,
+                    Some(spec.conv),
+                    FormatErrorReason::NestedLocalePattern,
+                )
+                .into());
+            }
+        }
+    }
+    Ok(format)
+}
+
+// Format a single conversion with the system strftime into a writer,
+// accepting an empty result
+fn system_strftime_lossy<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    format: &str,
+    tm: &tm,
+) -> Result<(), Error> {
+    match system_strftime_to(out, format, tm) {
+        Err(Error::InvalidFormatString) => Ok(()),
+        res => res,
+    }
+}
+
+// Format time with the system strftime into a writer, without allocating
+// when the format and the output are short
+fn system_strftime_to<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    format: &str,
+    tm: &tm,
+) -> Result<(), Error> {
+    const STACK_BUF_SIZE: usize = 256;
+
+    if format.len() < STACK_BUF_SIZE && !format.contains('\0') {
+        let mut c_format = [0u8; STACK_BUF_SIZE];
+        c_format[..format.len()].copy_from_slice(format.as_bytes());
+        let mut buf = [0u8; STACK_BUF_SIZE];
+        let len = unsafe {
+            strftime(
+                buf.as_mut_ptr() as *mut c_char,
+                buf.len(),
+                c_format.as_ptr() as *const c_char,
+                tm,
+            )
+        };
+        // A zero length is ambiguous, and is handled by the slow path
+        if len > 0 {
+            let formatted = std::str::from_utf8(&buf[..len]).map_err(|_| Error::Utf8Error)?;
+            return write_str(out, formatted);
+        }
+    }
+    write_str(out, &system_strftime(format, tm)?)
+}
+
+// Format time with the system strftime
+fn system_strftime(format: &str, tm: &tm) -> Result<String, Error> {
     let format_len = format.len();
     let format = CString::new(format).map_err(|_| Error::NullByteError)?;
     let mut buf_size = format_len;
@@ -500,34 +1251,29 @@ fn format_time_with_tm(format: &str, tm: &tm) -> Result<String, Error> {
 ///
 /// The standard format directives from strftime are supported.
 /// Additionally, the special text sequence '{ms}' will be replaced with the millisecond component.
+/// Literal braces are written `{{` and `}}`, and other `{...}` placeholders are rejected.
 ///
 /// Example: strftime_ms_utc("%Y-%m-%d %H:%M:%S.{ms}", ts_ms)
 ///
+/// ```rust
+/// let ts_ms = time_format::TimeStampMs::new(1747751445, 123);
+///
+/// let json = time_format::strftime_ms_utc(r#"{{"ts":"%FT%T.{ms}Z"}}"#, ts_ms).unwrap();
+/// assert_eq!(json, r#"{"ts":"2025-05-20T14:30:45.123Z"}"#);
+/// ```
+///
 /// This function will validate the format string before attempting to format the time.
 pub fn strftime_ms_utc(format: impl AsRef<str>, ts_ms: TimeStampMs) -> Result<String, Error> {
-    let format_str = format.as_ref();
-
-    // Validate the format string (validation also checks for balanced braces)
-    validate_format(format_str)?;
+    // Validate the format string
+    let format = check_format(format.as_ref())?;
 
-    // First, format the seconds part
-    // Skip validation in strftime_utc since we already did it
     let mut tm = MaybeUninit::<tm>::uninit();
     if unsafe { gmtime_r(&ts_ms.seconds, tm.as_mut_ptr()) }.is_null() {
         return Err(Error::TimeError);
     }
     let tm = unsafe { tm.assume_init() };
 
-    let seconds_formatted = format_time_with_tm(format_str, &tm)?;
-
-    // If the format contains the {ms} placeholder, replace it with the milliseconds
-    if format_str.contains("{ms}") {
-        // Format milliseconds with leading zeros
-        let ms_str = format!("{:03}", ts_ms.milliseconds);
-        Ok(seconds_formatted.replace("{ms}", &ms_str))
-    } else {
-        Ok(seconds_formatted)
-    }
+    format_time_with_tm(format, &tm, Fraction::millis(ts_ms.milliseconds), None)
 }
 
 /// Return the current time in the specified format, in the local time zone,
@@ -535,34 +1281,112 @@ pub fn strftime_ms_utc(format: impl AsRef<str>, ts_ms: TimeStampMs) -> Result<St
 ///
 /// The standard format directives from strftime are supported.
 /// Additionally, the special text sequence '{ms}' will be replaced with the millisecond component.
+/// Literal braces are written `{{` and `}}`, and other `{...}` placeholders are rejected.
 ///
 /// Example: strftime_ms_local("%Y-%m-%d %H:%M:%S.{ms}", ts_ms)
 ///
 /// This function will validate the format string before attempting to format the time.
 pub fn strftime_ms_local(format: impl AsRef<str>, ts_ms: TimeStampMs) -> Result<String, Error> {
-    let format_str = format.as_ref();
-
-    // Validate the format string (validation also checks for balanced braces)
-    validate_format(format_str)?;
+    // Validate the format string
+    let format = check_format(format.as_ref())?;
 
-    // First, format the seconds part
-    // Skip validation in strftime_local since we already did it
     let mut tm = MaybeUninit::<tm>::uninit();
     if unsafe { localtime_r(&ts_ms.seconds, tm.as_mut_ptr()) }.is_null() {
         return Err(Error::TimeError);
     }
     let tm = unsafe { tm.assume_init() };
 
-    let seconds_formatted = format_time_with_tm(format_str, &tm)?;
+    format_time_with_tm(format, &tm, Fraction::millis(ts_ms.milliseconds), None)
+}
 
-    // If the format contains the {ms} placeholder, replace it with the milliseconds
-    if format_str.contains("{ms}") {
-        // Format milliseconds with leading zeros
-        let ms_str = format!("{:03}", ts_ms.milliseconds);
-        Ok(seconds_formatted.replace("{ms}", &ms_str))
-    } else {
-        Ok(seconds_formatted)
+/// Return the time in the specified format, in the UTC time zone, using
+/// month and weekday names, AM/PM markers and date/time patterns from the
+/// given locale instead of the process-global C locale.
+///
+/// The locale applies to `%a`, `%A`, `%b`, `%h`, `%B`, `%p`, `%P`, `%c`,
+/// `%x`, `%X` and `%r`, and to the `E` and `O` modifiers, which render the
+/// eras and alternative numerals of the locale (see [`Locale`]).
+///
+/// ```rust
+/// use time_format::Locale;
+///
+/// let ts = 1747751445; // 2025-05-20T14:30:45Z
+///
+/// let es = time_format::strftime_utc_locale("%a %d %b %Y, %r", ts, &Locale::ES).unwrap();
+/// assert_eq!(es, "mar 20 may 2025, 02:30:45 p. m.");
+///
+/// let ja = time_format::strftime_utc_locale("%c (%a)", ts, &Locale::JA).unwrap();
+/// assert_eq!(ja, "2025年05月20日 14時30分45秒 (火)");
+/// ```
+pub fn strftime_utc_locale(
+    format: impl AsRef<str>,
+    ts_seconds: TimeStamp,
+    locale: &Locale,
+) -> Result<String, Error> {
+    strftime_ms_utc_locale(format, TimeStampMs::from_timestamp(ts_seconds), locale)
+}
+
+/// Return the time in the specified format, in the local time zone, using
+/// month and weekday names, AM/PM markers and date/time patterns from the
+/// given locale instead of the process-global C locale.
+///
+/// See [`strftime_utc_locale`] for the conversions affected by the locale.
+pub fn strftime_local_locale(
+    format: impl AsRef<str>,
+    ts_seconds: TimeStamp,
+    locale: &Locale,
+) -> Result<String, Error> {
+    strftime_ms_local_locale(format, TimeStampMs::from_timestamp(ts_seconds), locale)
+}
+
+/// Return the time in the specified format, in the UTC time zone, with
+/// support for the '{ms}' placeholder, using the given locale.
+///
+/// See [`strftime_utc_locale`] for the conversions affected by the locale.
+pub fn strftime_ms_utc_locale(
+    format: impl AsRef<str>,
+    ts_ms: TimeStampMs,
+    locale: &Locale,
+) -> Result<String, Error> {
+    let format = check_format(format.as_ref())?;
+
+    let mut tm = MaybeUninit::<tm>::uninit();
+    if unsafe { gmtime_r(&ts_ms.seconds, tm.as_mut_ptr()) }.is_null() {
+        return Err(Error::TimeError);
     }
+    let tm = unsafe { tm.assume_init() };
+
+    format_time_with_tm(
+        format,
+        &tm,
+        Fraction::millis(ts_ms.milliseconds),
+        Some(locale),
+    )
+}
+
+/// Return the time in the specified format, in the local time zone, with
+/// support for the '{ms}' placeholder, using the given locale.
+///
+/// See [`strftime_utc_locale`] for the conversions affected by the locale.
+pub fn strftime_ms_local_locale(
+    format: impl AsRef<str>,
+    ts_ms: TimeStampMs,
+    locale: &Locale,
+) -> Result<String, Error> {
+    let format = check_format(format.as_ref())?;
+
+    let mut tm = MaybeUninit::<tm>::uninit();
+    if unsafe { localtime_r(&ts_ms.seconds, tm.as_mut_ptr()) }.is_null() {
+        return Err(Error::TimeError);
+    }
+    let tm = unsafe { tm.assume_init() };
+
+    format_time_with_tm(
+        format,
+        &tm,
+        Fraction::millis(ts_ms.milliseconds),
+        Some(locale),
+    )
 }
 
 /// Format a timestamp according to ISO 8601 format in UTC.
@@ -630,9 +1454,95 @@ pub fn format_iso8601_ms_local(ts_ms: TimeStampMs) -> Result<String, Error> {
     })
 }
 
+/// Format a timestamp according to the ISO 8601 basic format, without
+/// separators: `YYYYMMDDThhmmss`, followed by the fractional seconds at the
+/// requested precision, and `Z` in UTC or the offset as `±hhmm` otherwise.
+///
+/// The result has no colons, so it can be used in file names and object
+/// keys. In UTC, such names sort in chronological order.
+///
+/// The timestamp is a [`TimeStampUs`], or a [`TimeStampMs`], whose
+/// microseconds are its milliseconds.
+///
+/// ```rust
+/// use time_format::{Precision, TimeStampUs, TimeZone};
+///
+/// let ts_us = TimeStampUs::new(1747751445, 123456);
+/// let basic = |precision, zone| time_format::format_iso8601_basic(ts_us, precision, zone).unwrap();
+/// assert_eq!(basic(Precision::Seconds, TimeZone::Utc), "20250520T143045Z");
+/// assert_eq!(basic(Precision::Milliseconds, TimeZone::Utc), "20250520T143045.123Z");
+/// assert_eq!(basic(Precision::Microseconds, TimeZone::Utc), "20250520T143045.123456Z");
+/// assert_eq!(basic(Precision::Seconds, TimeZone::FixedOffset(7200)), "20250520T163045+0200");
+/// ```
+pub fn format_iso8601_basic(
+    ts: impl Into<TimeStampUs>,
+    precision: Precision,
+    zone: TimeZone,
+) -> Result<String, Error> {
+    let ts_us = ts.into();
+    let fraction = match precision {
+        Precision::Seconds => None,
+        Precision::Milliseconds => Some(Fraction::millis((ts_us.microseconds / 1000) as u16)),
+        Precision::Microseconds => Some(Fraction::micros(ts_us.microseconds)),
+    };
+    let format = match zone {
+        TimeZone::Utc => DateFormat::ISO8601Basic,
+        _ => DateFormat::ISO8601BasicOffset,
+    };
+    FormatRef::from(format).format(ts_us.seconds, fraction, zone)
+}
+
+/// Time zone in which a timestamp is rendered.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum TimeZone {
+    /// Coordinated Universal Time.
+    #[default]
+    Utc,
+    /// The local time zone of the process.
+    Local,
+    /// A fixed offset from UTC in seconds, positive east of Greenwich.
+    ///
+    /// `%z` renders the offset. Fixed offsets have no zone name, so `%Z`
+    /// should not be used with them: its output depends on the system.
+    FixedOffset(i32),
+}
+
+/// Precision of the fractional seconds of a timestamp.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Default)]
+pub enum Precision {
+    /// Whole seconds.
+    Seconds,
+    /// Milliseconds.
+    #[default]
+    Milliseconds,
+    /// Microseconds, from a [`TimeStampUs`].
+    Microseconds,
+}
+
+impl Precision {
+    // Fractional seconds of a timestamp at this precision, zeros for seconds
+    #[cfg(any(feature = "log", feature = "tracing-subscriber"))]
+    pub(crate) fn fraction(self, ts_us: TimeStampUs) -> Fraction {
+        match self {
+            Self::Seconds => Fraction::millis(0),
+            Self::Milliseconds => Fraction::millis((ts_us.microseconds / 1000) as u16),
+            Self::Microseconds => Fraction::micros(ts_us.microseconds),
+        }
+    }
+}
+
 /// Format types for common date strings
 ///
 /// This enum provides common date and time format patterns.
+///
+/// Format strings loaded at run time can be used as a [`CustomFormat`]
+/// instead of [`DateFormat::Custom`]: the functions taking a format accept
+/// both, as a [`FormatRef`].
+///
+/// With the `serde` feature, formats are serialized as their name or format
+/// string, and built-in formats are deserialized with
+/// [`DateFormat::from_name`]. Custom formats can't be deserialized; use a
+/// [`CustomFormat`] instead.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum DateFormat {
     /// RFC 3339 (similar to ISO 8601) format: "2025-05-20T14:30:45Z" or "2025-05-20T14:30:45-05:00"
@@ -657,6 +1567,20 @@ pub enum DateFormat {
     LongTime,
     /// Date and time: "2025-05-20 14:30:45"
     DateTime,
+    /// BSD syslog (RFC 3164) format, without a year and with a space-padded
+    /// day: "May 20 14:30:45", "May  5 14:30:45"
+    RFC3164,
+    /// Syslog (RFC 5424) format, with as many digits of fractional seconds
+    /// as the timestamp has: "2025-05-20T14:30:45.123+02:00", or
+    /// "2025-05-20T14:30:45.123456+02:00" with microseconds
+    RFC5424,
+    /// Common Log Format, as in Apache and nginx access logs:
+    /// "[20/May/2025:14:30:45 -0500]"
+    CommonLog,
+    /// ISO 8601 basic format in UTC, safe in file names: "20250520T143045Z"
+    ISO8601Basic,
+    /// ISO 8601 basic format with an offset: "20250520T093045-0500"
+    ISO8601BasicOffset,
     /// Custom format string
     Custom(&'static str),
 }
@@ -676,13 +1600,427 @@ impl DateFormat {
             Self::ShortTime => "%H:%M",
             Self::LongTime => "%H:%M:%S",
             Self::DateTime => "%Y-%m-%d %H:%M:%S",
+            Self::RFC3164 => "%b %e %H:%M:%S",
+            Self::RFC5424 => "%Y-%m-%dT%H:%M:%S%:z",
+            Self::CommonLog => "[%d/%b/%Y:%H:%M:%S %z]",
+            Self::ISO8601Basic => "%Y%m%dT%H%M%SZ",
+            Self::ISO8601BasicOffset => "%Y%m%dT%H%M%S%z",
             Self::Custom(fmt) => fmt,
         }
     }
+
+    /// Get the format string used to render this format, optionally with
+    /// fractional seconds for the formats that can reasonably include them
+    fn pattern(&self, with_fraction: bool) -> &'static str {
+        match self {
+            Self::RFC3339 if with_fraction => "%Y-%m-%dT%H:%M:%S.{ms}%:z",
+            Self::RFC3339 => "%Y-%m-%dT%H:%M:%S%:z",
+            Self::SQL | Self::DateTime if with_fraction => "%Y-%m-%d %H:%M:%S.{ms}",
+            Self::LongTime if with_fraction => "%H:%M:%S.{ms}",
+            Self::RFC5424 if with_fraction => "%Y-%m-%dT%H:%M:%S.{ms}%:z",
+            Self::ISO8601Basic if with_fraction => "%Y%m%dT%H%M%S.{ms}Z",
+            Self::ISO8601BasicOffset if with_fraction => "%Y%m%dT%H%M%S.{ms}%z",
+            _ => self.get_format_string(),
+        }
+    }
+
+    /// Get the format string used to render this format with fractional
+    /// seconds of the given precision, in which `{ms}` stands for all the
+    /// digits of the fraction
+    #[cfg(any(feature = "log", feature = "tracing-subscriber"))]
+    pub(crate) fn pattern_for(&self, precision: Precision) -> &'static str {
+        self.pattern(precision != Precision::Seconds)
+    }
+
+    // Time zone to render this format in when `zone` is requested:
+    // HTTP dates are always in GMT/UTC, and so are the ISO 8601 basic dates
+    // that end with `Z`
+    fn zone_for(&self, zone: TimeZone) -> TimeZone {
+        match self {
+            Self::HTTP | Self::ISO8601Basic => TimeZone::Utc,
+            _ => zone,
+        }
+    }
+
+    // Locale of the names in this format: the formats defined by standards
+    // use English names, whatever the locale of the process
+    fn locale(&self) -> Option<&'static Locale> {
+        match self {
+            Self::RFC2822 | Self::HTTP | Self::RFC3164 | Self::CommonLog => Some(&Locale::EN),
+            _ => None,
+        }
+    }
+
+    // Format a timestamp directly into a writer, with the fractional
+    // seconds if given and the format can include them
+    fn write_to<W: fmt::Write + ?Sized>(
+        &self,
+        out: &mut W,
+        ts_seconds: TimeStamp,
+        fraction: Option<Fraction>,
+        zone: TimeZone,
+    ) -> Result<(), Error> {
+        write_strftime(
+            out,
+            self.pattern(fraction.is_some()),
+            ts_seconds,
+            fraction.unwrap_or(Fraction::millis(0)),
+            self.zone_for(zone),
+            self.locale(),
+        )
+    }
+}
+
+impl DateFormat {
+    /// All the built-in formats.
+    ///
+    /// ```rust
+    /// use time_format::DateFormat;
+    ///
+    /// for format in DateFormat::ALL {
+    ///     println!("{:<10} {}", format, format.example().unwrap());
+    /// }
+    /// assert_eq!(DateFormat::ALL[0].example().unwrap(), "2025-05-20T14:30:45.123+00:00");
+    /// ```
+    pub const ALL: [DateFormat; 16] = [
+        DateFormat::RFC3339,
+        DateFormat::RFC2822,
+        DateFormat::HTTP,
+        DateFormat::SQL,
+        DateFormat::US,
+        DateFormat::European,
+        DateFormat::ShortDate,
+        DateFormat::LongDate,
+        DateFormat::ShortTime,
+        DateFormat::LongTime,
+        DateFormat::DateTime,
+        DateFormat::RFC3164,
+        DateFormat::RFC5424,
+        DateFormat::CommonLog,
+        DateFormat::ISO8601Basic,
+        DateFormat::ISO8601BasicOffset,
+    ];
+
+    /// The built-in format with a name or an alias, ignoring case, `-`, `_`
+    /// and spaces. The canonical names are those of the variants, such as
+    /// `RFC3339` or `ShortDate`.
+    ///
+    /// Aliases are `ISO8601` and `ISO` for `RFC3339`, `Email` for `RFC2822`,
+    /// `RFC7231` and `IMF-fixdate` for `HTTP`, `EU` for `European`, `Syslog`
+    /// and `BSD-syslog` for `RFC3164`, `Syslog5424` for `RFC5424`, and `CLF`
+    /// and `Apache` for `CommonLog`, and `Basic` and `Compact` for
+    /// `ISO8601Basic`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name: String = name
+            .chars()
+            .filter(|c| !matches!(c, '-' | '_' | ' '))
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let format = match name.as_str() {
+            "rfc3339" | "iso8601" | "iso" => DateFormat::RFC3339,
+            "rfc2822" | "email" => DateFormat::RFC2822,
+            "http" | "rfc7231" | "imffixdate" => DateFormat::HTTP,
+            "sql" => DateFormat::SQL,
+            "us" => DateFormat::US,
+            "european" | "eu" => DateFormat::European,
+            "shortdate" => DateFormat::ShortDate,
+            "longdate" => DateFormat::LongDate,
+            "shorttime" => DateFormat::ShortTime,
+            "longtime" => DateFormat::LongTime,
+            "datetime" => DateFormat::DateTime,
+            "rfc3164" | "syslog" | "bsdsyslog" => DateFormat::RFC3164,
+            "rfc5424" | "syslog5424" => DateFormat::RFC5424,
+            "commonlog" | "clf" | "apache" => DateFormat::CommonLog,
+            "iso8601basic" | "basic" | "compact" => DateFormat::ISO8601Basic,
+            "iso8601basicoffset" | "basicoffset" => DateFormat::ISO8601BasicOffset,
+            _ => return None,
+        };
+        Some(format)
+    }
+
+    /// A built-in format by name, as [`DateFormat::from_name`] finds it, or
+    /// else a custom format if the string contains `%`.
+    ///
+    /// Custom formats are validated. Use a [`CustomFormat`] for strings that
+    /// aren't `'static`.
+    ///
+    /// ```rust
+    /// use time_format::{DateFormat, Error};
+    ///
+    /// assert_eq!(DateFormat::from_name_or_pattern("rfc2822"), Ok(DateFormat::RFC2822));
+    /// assert_eq!(DateFormat::from_name_or_pattern("%d.%m.%Y"), Ok(DateFormat::Custom("%d.%m.%Y")));
+    /// assert_eq!(DateFormat::from_name_or_pattern("rfc9999"), Err(Error::ParseError));
+    /// assert!(DateFormat::from_name_or_pattern("%d.%m.%Y {").is_err());
+    /// ```
+    pub fn from_name_or_pattern(s: &'static str) -> Result<Self, Error> {
+        match DateFormat::from_name(s) {
+            Some(format) => Ok(format),
+            None if s.contains('%') => {
+                validate_format(s)?;
+                Ok(DateFormat::Custom(s))
+            }
+            None => Err(Error::ParseError),
+        }
+    }
+
+    /// The canonical name of a built-in format, or `None` for a custom
+    /// format.
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match self {
+            Self::RFC3339 => "RFC3339",
+            Self::RFC2822 => "RFC2822",
+            Self::HTTP => "HTTP",
+            Self::SQL => "SQL",
+            Self::US => "US",
+            Self::European => "European",
+            Self::ShortDate => "ShortDate",
+            Self::LongDate => "LongDate",
+            Self::ShortTime => "ShortTime",
+            Self::LongTime => "LongTime",
+            Self::DateTime => "DateTime",
+            Self::RFC3164 => "RFC3164",
+            Self::RFC5424 => "RFC5424",
+            Self::CommonLog => "CommonLog",
+            Self::ISO8601Basic => "ISO8601Basic",
+            Self::ISO8601BasicOffset => "ISO8601BasicOffset",
+            Self::Custom(_) => return None,
+        };
+        Some(name)
+    }
+
+    /// The rendering of 2025-05-20 14:30:45.123 UTC in this format, with
+    /// milliseconds where the format can include them.
+    pub fn example(&self) -> Result<String, Error> {
+        FormatRef::from(*self).format(1747751445, Some(Fraction::millis(123)), TimeZone::Utc)
+    }
+}
+
+impl FromStr for DateFormat {
+    type Err = Error;
+
+    /// Find a built-in format by name, as [`DateFormat::from_name`] does.
+    ///
+    /// Custom formats can't be parsed this way, since they borrow their
+    /// format string: see [`DateFormat::from_name_or_pattern`] and
+    /// [`CustomFormat`].
+    fn from_str(s: &str) -> Result<Self, Error> {
+        DateFormat::from_name(s).ok_or(Error::ParseError)
+    }
+}
+
+impl fmt::Display for DateFormat {
+    /// Write the canonical name of a built-in format, or the format string
+    /// of a custom format.
+    ///
+    /// ```rust
+    /// use time_format::DateFormat;
+    ///
+    /// assert_eq!(DateFormat::ShortDate.to_string(), "ShortDate");
+    /// assert_eq!("short-date".parse::<DateFormat>().unwrap(), DateFormat::ShortDate);
+    /// assert_eq!(DateFormat::Custom("%d.%m.%Y").to_string(), "%d.%m.%Y");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Custom(format) => f.write_str(format),
+            format => f.write_str(format.name().unwrap_or_default()),
+        }
+    }
+}
+
+/// A custom strftime format string, owned and validated when created.
+///
+/// Format strings loaded at run time, e.g. from a configuration file, can
+/// be checked once when they are loaded, and then used wherever a
+/// [`DateFormat`] is accepted, for as long as they are kept. The format
+/// string is split into tokens when it is validated, so that it isn't
+/// parsed again for every timestamp. With the `serde` feature, it is
+/// deserialized from a string, and validated.
+///
+/// ```rust
+/// use time_format::{CustomFormat, TimeStampMs};
+///
+/// let config = String::from("%d.%m.%Y %H:%M:%S.{ms}");
+/// let format = CustomFormat::new(config).unwrap();
+///
+/// let ts_ms = TimeStampMs::new(1747751445, 123);
+/// let formatted = time_format::format_common_ms_utc(ts_ms, &format).unwrap();
+/// assert_eq!(formatted, "20.05.2025 14:30:45.123");
+///
+/// assert!("%H:%M:%S.{us}".parse::<CustomFormat>().is_err());
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CustomFormat {
+    format: String,
+    // Whether the system strftime can format it as is
+    plain: bool,
+    tokens: Vec<strftime::TokenSpan>,
+}
+
+impl CustomFormat {
+    /// Validate a format string, as [`validate_format`] does.
+    pub fn new(format: impl Into<String>) -> Result<Self, Error> {
+        let format = format.into();
+        let plain = check_format(&format)?.plain;
+        let tokens = strftime::spans(&format)?;
+        Ok(Self {
+            format,
+            plain,
+            tokens,
+        })
+    }
+
+    /// The format string.
+    pub fn as_str(&self) -> &str {
+        &self.format
+    }
+
+    /// A [`FormatRef`] borrowing this format.
+    pub fn format_ref(&self) -> FormatRef<'_> {
+        FormatRef::Custom(self)
+    }
+
+    // The tokens of the format string, as split when it was validated
+    pub(crate) fn tokens(&self) -> impl Iterator<Item = strftime::Token<'_>> {
+        self.tokens.iter().map(move |span| span.token(&self.format))
+    }
+
+    // Format a timestamp directly into a writer
+    fn write_to<W: fmt::Write + ?Sized>(
+        &self,
+        out: &mut W,
+        ts_seconds: TimeStamp,
+        fraction: Fraction,
+        zone: TimeZone,
+    ) -> Result<(), Error> {
+        let tm = tm_in_zone(ts_seconds, zone)?;
+        if self.plain {
+            return system_strftime_to(out, &self.format, &tm);
+        }
+        write_tokens(out, self.tokens(), &tm, fraction, None)
+    }
+}
+
+impl FromStr for CustomFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for CustomFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format)
+    }
+}
+
+impl AsRef<str> for CustomFormat {
+    fn as_ref(&self) -> &str {
+        &self.format
+    }
+}
+
+/// A [`DateFormat`], or a borrowed [`CustomFormat`].
+///
+/// Functions taking a format accept either, through `Into<FormatRef>`.
+///
+/// ```rust
+/// use time_format::{CustomFormat, DateFormat, FormatRef};
+///
+/// let custom: CustomFormat = "%d.%m.%Y".parse().unwrap();
+/// let formats = [FormatRef::from(DateFormat::SQL), FormatRef::from(&custom)];
+/// let rendered: Vec<String> = formats
+///     .iter()
+///     .map(|format| format.display(1747751445).to_string())
+///     .collect();
+/// assert_eq!(rendered, ["2025-05-20 14:30:45", "20.05.2025"]);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FormatRef<'a> {
+    /// A built-in format, or a `'static` custom format string.
+    Date(DateFormat),
+    /// A custom format string validated at run time.
+    Custom(&'a CustomFormat),
+}
+
+impl FormatRef<'_> {
+    // Time zone to render this format in when `zone` is requested
+    #[cfg(any(feature = "log", feature = "tracing-subscriber"))]
+    pub(crate) fn zone_for(&self, zone: TimeZone) -> TimeZone {
+        match self {
+            Self::Date(format) => format.zone_for(zone),
+            Self::Custom(_) => zone,
+        }
+    }
+
+    // Locale of the names in this format
+    #[cfg(any(feature = "log", feature = "tracing-subscriber"))]
+    pub(crate) fn locale(&self) -> Option<&'static Locale> {
+        match self {
+            Self::Date(format) => format.locale(),
+            Self::Custom(_) => None,
+        }
+    }
+
+    // Format a timestamp directly into a writer, with the fractional
+    // seconds if given and the format can include them
+    pub(crate) fn write_to<W: fmt::Write + ?Sized>(
+        &self,
+        out: &mut W,
+        ts_seconds: TimeStamp,
+        fraction: Option<Fraction>,
+        zone: TimeZone,
+    ) -> Result<(), Error> {
+        match self {
+            Self::Date(format) => format.write_to(out, ts_seconds, fraction, zone),
+            Self::Custom(format) => format.write_to(
+                out,
+                ts_seconds,
+                fraction.unwrap_or(Fraction::millis(0)),
+                zone,
+            ),
+        }
+    }
+
+    fn format(
+        &self,
+        ts_seconds: TimeStamp,
+        fraction: Option<Fraction>,
+        zone: TimeZone,
+    ) -> Result<String, Error> {
+        let mut out = String::new();
+        self.write_to(&mut out, ts_seconds, fraction, zone)?;
+        Ok(out)
+    }
+}
+
+impl From<DateFormat> for FormatRef<'_> {
+    fn from(format: DateFormat) -> Self {
+        Self::Date(format)
+    }
+}
+
+impl<'a> From<&'a CustomFormat> for FormatRef<'a> {
+    fn from(format: &'a CustomFormat) -> Self {
+        Self::Custom(format)
+    }
+}
+
+impl fmt::Display for FormatRef<'_> {
+    /// Write the name or the format string of the format, as the `Display`
+    /// implementations of [`DateFormat`] and [`CustomFormat`] do.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Date(format) => format.fmt(f),
+            Self::Custom(format) => format.fmt(f),
+        }
+    }
 }
 
 /// Format a timestamp using a common date format in UTC timezone
 ///
+/// The format is a [`DateFormat`], or a reference to a [`CustomFormat`].
+///
 /// Examples:
 /// ```rust
 /// let ts = time_format::now().unwrap();
@@ -696,23 +2034,11 @@ impl DateFormat {
 /// // Format with a custom format
 /// let custom = time_format::format_common_utc(ts, time_format::DateFormat::Custom("%Y-%m-%d")).unwrap();
 /// ```
-pub fn format_common_utc(ts: TimeStamp, format: DateFormat) -> Result<String, Error> {
-    let format_str = format.get_format_string();
-
-    match format {
-        DateFormat::RFC3339 => {
-            // Handle RFC3339 specially to ensure proper timezone formatting
-            strftime_utc(format_str, ts).map(|s| {
-                if s.ends_with('0') || s.chars().last().unwrap().is_ascii_digit() {
-                    let len = s.len();
-                    format!("{}:{}", &s[..len - 2], &s[len - 2..])
-                } else {
-                    s
-                }
-            })
-        }
-        _ => strftime_utc(format_str, ts),
-    }
+pub fn format_common_utc<'a>(
+    ts: TimeStamp,
+    format: impl Into<FormatRef<'a>>,
+) -> Result<String, Error> {
+    format.into().format(ts, None, TimeZone::Utc)
 }
 
 /// Format a timestamp using a common date format in local timezone
@@ -727,27 +2053,11 @@ pub fn format_common_utc(ts: TimeStamp, format: DateFormat) -> Result<String, Er
 /// // Format as US date
 /// let us_date = time_format::format_common_local(ts, time_format::DateFormat::US).unwrap();
 /// ```
-pub fn format_common_local(ts: TimeStamp, format: DateFormat) -> Result<String, Error> {
-    let format_str = format.get_format_string();
-
-    match format {
-        DateFormat::RFC3339 => {
-            // Handle RFC3339 specially to ensure proper timezone formatting
-            strftime_local(format_str, ts).map(|s| {
-                if s.ends_with('0') || s.chars().last().unwrap().is_ascii_digit() {
-                    let len = s.len();
-                    format!("{}:{}", &s[..len - 2], &s[len - 2..])
-                } else {
-                    s
-                }
-            })
-        }
-        DateFormat::HTTP => {
-            // HTTP dates are always in GMT/UTC, so redirect to the UTC version
-            format_common_utc(ts, format)
-        }
-        _ => strftime_local(format_str, ts),
-    }
+pub fn format_common_local<'a>(
+    ts: TimeStamp,
+    format: impl Into<FormatRef<'a>>,
+) -> Result<String, Error> {
+    format.into().format(ts, None, TimeZone::Local)
 }
 
 /// Format a timestamp with millisecond precision using a common date format in UTC timezone
@@ -763,31 +2073,14 @@ pub fn format_common_local(ts: TimeStamp, format: DateFormat) -> Result<String,
 /// let rfc3339 = time_format::format_common_ms_utc(ts_ms, time_format::DateFormat::RFC3339).unwrap();
 /// // Example: "2025-05-20T14:30:45.123Z"
 /// ```
-pub fn format_common_ms_utc(ts_ms: TimeStampMs, format: DateFormat) -> Result<String, Error> {
-    // For formats that can reasonably include milliseconds, add them
-    let format_str = match format {
-        DateFormat::RFC3339 => "%Y-%m-%dT%H:%M:%S.{ms}%z",
-        DateFormat::SQL => "%Y-%m-%d %H:%M:%S.{ms}",
-        DateFormat::DateTime => "%Y-%m-%d %H:%M:%S.{ms}",
-        DateFormat::LongTime => "%H:%M:%S.{ms}",
-        DateFormat::Custom(fmt) => fmt,
-        _ => format.get_format_string(), // Use standard format for others
-    };
-
-    match format {
-        DateFormat::RFC3339 => {
-            // Handle RFC3339 specially for timezone formatting
-            strftime_ms_utc(format_str, ts_ms).map(|s| {
-                if s.ends_with('0') || s.chars().last().unwrap().is_ascii_digit() {
-                    let len = s.len();
-                    format!("{}:{}", &s[..len - 2], &s[len - 2..])
-                } else {
-                    s
-                }
-            })
-        }
-        _ => strftime_ms_utc(format_str, ts_ms),
-    }
+pub fn format_common_ms_utc<'a>(
+    ts_ms: TimeStampMs,
+    format: impl Into<FormatRef<'a>>,
+) -> Result<String, Error> {
+    let fraction = Fraction::millis(ts_ms.milliseconds);
+    format
+        .into()
+        .format(ts_ms.seconds, Some(fraction), TimeZone::Utc)
 }
 
 /// Format a timestamp with millisecond precision using a common date format in local timezone
@@ -803,33 +2096,197 @@ pub fn format_common_ms_utc(ts_ms: TimeStampMs, format: DateFormat) -> Result<St
 /// let local_time = time_format::format_common_ms_local(ts_ms, time_format::DateFormat::RFC3339).unwrap();
 /// // Example: "2025-05-20T09:30:45.123-05:00"
 /// ```
-pub fn format_common_ms_local(ts_ms: TimeStampMs, format: DateFormat) -> Result<String, Error> {
-    // For formats that can reasonably include milliseconds, add them
-    let format_str = match format {
-        DateFormat::RFC3339 => "%Y-%m-%dT%H:%M:%S.{ms}%z",
-        DateFormat::SQL => "%Y-%m-%d %H:%M:%S.{ms}",
-        DateFormat::DateTime => "%Y-%m-%d %H:%M:%S.{ms}",
-        DateFormat::LongTime => "%H:%M:%S.{ms}",
-        DateFormat::Custom(fmt) => fmt,
-        _ => format.get_format_string(), // Use standard format for others
-    };
+pub fn format_common_ms_local<'a>(
+    ts_ms: TimeStampMs,
+    format: impl Into<FormatRef<'a>>,
+) -> Result<String, Error> {
+    let fraction = Fraction::millis(ts_ms.milliseconds);
+    format
+        .into()
+        .format(ts_ms.seconds, Some(fraction), TimeZone::Local)
+}
 
-    match format {
-        DateFormat::RFC3339 => {
-            // Handle RFC3339 specially for timezone formatting
-            strftime_ms_local(format_str, ts_ms).map(|s| {
-                if s.ends_with('0') || s.chars().last().unwrap().is_ascii_digit() {
-                    let len = s.len();
-                    format!("{}:{}", &s[..len - 2], &s[len - 2..])
-                } else {
-                    s
-                }
-            })
-        }
-        DateFormat::HTTP => {
-            // HTTP dates are always in GMT/UTC, so redirect to the UTC version
-            format_common_ms_utc(ts_ms, format)
-        }
-        _ => strftime_ms_local(format_str, ts_ms),
+/// Format a timestamp with microsecond precision using a common date format in UTC timezone
+///
+/// This function extends common date formats to include microseconds where
+/// appropriate, as [`format_common_ms_utc`] does with milliseconds.
+///
+/// Examples:
+/// ```rust
+/// use time_format::{DateFormat, TimeStampUs};
+///
+/// let ts_us = TimeStampUs::new(1747751445, 123456);
+/// let syslog = time_format::format_common_us_utc(ts_us, DateFormat::RFC5424).unwrap();
+/// assert_eq!(syslog, "2025-05-20T14:30:45.123456+00:00");
+/// ```
+pub fn format_common_us_utc<'a>(
+    ts_us: TimeStampUs,
+    format: impl Into<FormatRef<'a>>,
+) -> Result<String, Error> {
+    let fraction = Fraction::micros(ts_us.microseconds);
+    format
+        .into()
+        .format(ts_us.seconds, Some(fraction), TimeZone::Utc)
+}
+
+/// Format a timestamp with microsecond precision using a common date format in local timezone
+///
+/// This function extends common date formats to include microseconds where
+/// appropriate, as [`format_common_ms_local`] does with milliseconds.
+pub fn format_common_us_local<'a>(
+    ts_us: TimeStampUs,
+    format: impl Into<FormatRef<'a>>,
+) -> Result<String, Error> {
+    let fraction = Fraction::micros(ts_us.microseconds);
+    format
+        .into()
+        .format(ts_us.seconds, Some(fraction), TimeZone::Local)
+}
+
+/// Parse an RFC 3339 date and time, with any UTC offset.
+///
+/// A time and an offset are required. Fractions of a second are truncated
+/// to milliseconds.
+///
+/// ```rust
+/// use time_format::TimeStampMs;
+///
+/// let ts_ms = time_format::parse_rfc3339("2025-05-20T09:30:45.123-05:00").unwrap();
+/// assert_eq!(ts_ms, TimeStampMs::new(1747751445, 123));
+/// assert!(time_format::parse_rfc3339("2025-05-20T09:30:45").is_err());
+/// ```
+pub fn parse_rfc3339(s: impl AsRef<str>) -> Result<TimeStampMs, Error> {
+    parse::parse_rfc3339(s.as_ref())
+}
+
+/// Parse an HTTP date (RFC 7231): `Tue, 20 May 2025 14:30:45 GMT`.
+///
+/// ```rust
+/// let ts = time_format::parse_http_date("Tue, 20 May 2025 14:30:45 GMT").unwrap();
+/// assert_eq!(ts, 1747751445);
+/// ```
+pub fn parse_http_date(s: impl AsRef<str>) -> Result<TimeStamp, Error> {
+    parse::parse_http_date(s.as_ref()).map(|ts| ts.seconds)
+}
+
+/// Parse a BSD syslog (RFC 3164) date: `May 20 14:30:45`, with the day
+/// padded with a space or a zero, in the given time zone.
+///
+/// Syslog dates have no year: it is the year of `reference`, unless that
+/// would put the date more than a month after it, in which case it is the
+/// previous year. Pass the time at which the message was received, or
+/// [`now`].
+///
+/// ```rust
+/// use time_format::TimeZone;
+///
+/// // Received on 2025-06-01
+/// let ts = time_format::parse_rfc3164("May 20 14:30:45", 1748736000, TimeZone::Utc).unwrap();
+/// assert_eq!(ts, 1747751445);
+///
+/// // Received on 2026-01-05: December is in the previous year
+/// let ts = time_format::parse_rfc3164("Dec 31 23:59:59", 1767571200, TimeZone::Utc).unwrap();
+/// assert_eq!(ts, 1767225599);
+///
+/// let ts = time_format::parse_rfc3164("May  5 16:30:45", 1748736000, TimeZone::FixedOffset(7200)).unwrap();
+/// assert_eq!(ts, 1746455445);
+/// ```
+pub fn parse_rfc3164(
+    s: impl AsRef<str>,
+    reference: TimeStamp,
+    zone: TimeZone,
+) -> Result<TimeStamp, Error> {
+    let (fields, rest) = parse::parse_rfc3164_prefix(s.as_ref(), reference)?;
+    if !rest.is_empty() || fields.nanosecond != 0 {
+        return Err(Error::ParseError);
     }
+    fields.to_timestamp_in(zone).map(|ts_ms| ts_ms.seconds)
+}
+
+/// Parse a syslog (RFC 5424) timestamp: `2025-05-20T14:30:45.123456+02:00`.
+///
+/// Fractional seconds have at most six digits, and are kept with
+/// microsecond precision, so that timestamps formatted with
+/// [`DateFormat::RFC5424`] parse back exactly. The offset is `Z` or has a
+/// colon.
+///
+/// ```rust
+/// use time_format::{DateFormat, TimeStampUs};
+///
+/// let ts_us = time_format::parse_rfc5424("2025-05-20T16:30:45.123456+02:00").unwrap();
+/// assert_eq!(ts_us, TimeStampUs::new(1747751445, 123456));
+/// assert!(time_format::parse_rfc5424("2025-05-20T14:30:45.1234567Z").is_err());
+///
+/// let formatted = time_format::format_common_us_utc(ts_us, DateFormat::RFC5424).unwrap();
+/// assert_eq!(formatted, "2025-05-20T14:30:45.123456+00:00");
+/// assert_eq!(time_format::parse_rfc5424(&formatted).unwrap(), ts_us);
+/// ```
+pub fn parse_rfc5424(s: impl AsRef<str>) -> Result<TimeStampUs, Error> {
+    parse::parse_rfc5424(s.as_ref())
+}
+
+/// Parse a Common Log Format timestamp, as in Apache and nginx access
+/// logs: `[20/May/2025:14:30:45 -0500]`, with or without the brackets.
+///
+/// Returns the timestamp and the UTC offset in seconds, which can be used
+/// to render other timestamps in the same time zone with
+/// [`TimeZone::FixedOffset`].
+///
+/// ```rust
+/// let (ts, offset) = time_format::parse_common_log("[20/May/2025:09:30:45 -0500]").unwrap();
+/// assert_eq!(ts, 1747751445);
+/// assert_eq!(offset, -5 * 3600);
+/// ```
+pub fn parse_common_log(s: impl AsRef<str>) -> Result<(TimeStamp, i32), Error> {
+    parse::parse_common_log(s.as_ref()).map(|(ts_ms, offset)| (ts_ms.seconds, offset))
+}
+
+/// Parse an ISO 8601 date and time in basic format: `20250520T143045Z`,
+/// `20250520T143045.123Z` or `20250520T093045-0500`.
+///
+/// A time and an offset (`Z`, `±hhmm` or `±hh`) are required. Fractions of
+/// a second are truncated to microseconds, so that the output of
+/// [`format_iso8601_basic`] parses back exactly.
+///
+/// ```rust
+/// use time_format::{Precision, TimeStampUs, TimeZone};
+///
+/// let ts_us = time_format::parse_iso8601_basic("20250520T143045.123456Z").unwrap();
+/// assert_eq!(ts_us, TimeStampUs::new(1747751445, 123456));
+/// let basic = time_format::format_iso8601_basic(ts_us, Precision::Microseconds, TimeZone::Utc).unwrap();
+/// assert_eq!(basic, "20250520T143045.123456Z");
+/// assert_eq!(time_format::parse_iso8601_basic(&basic).unwrap(), ts_us);
+/// let ts_us = time_format::parse_iso8601_basic("20250520T093045-0500").unwrap();
+/// assert_eq!(ts_us, TimeStampUs::from_timestamp(1747751445));
+/// assert!(time_format::parse_iso8601_basic("2025-05-20T14:30:45Z").is_err());
+/// ```
+pub fn parse_iso8601_basic(s: impl AsRef<str>) -> Result<TimeStampUs, Error> {
+    parse::parse_iso8601_basic(s.as_ref())
+}
+
+/// Parse a date and time with a strftime format string, as the C `strptime`
+/// function does.
+///
+/// Month and weekday names and AM/PM markers are in English, whitespace in
+/// the format matches any amount of whitespace, and numeric fields accept
+/// any padding. The `{ms}` placeholder matches fractional seconds of any
+/// length, truncated to milliseconds, and may only appear once. `%z` accepts `Z`, `±hh:mm`, `±hhmm`
+/// and `±hh`, and `%Z` only `UTC`, `GMT` and `Z`. Without an offset, the
+/// date and time are in UTC; missing date fields default to January 1,
+/// 1970. Directives that can't be parsed, such as `%c` or `%U`, are
+/// rejected with [`Error::InvalidFormatString`].
+///
+/// ```rust
+/// use time_format::TimeStampMs;
+///
+/// let ts_ms = time_format::parse_with_format("20/May/2025:09:30:45 -0500", "%d/%b/%Y:%T %z").unwrap();
+/// assert_eq!(ts_ms, TimeStampMs::from_timestamp(1747751445));
+///
+/// let ts_ms = time_format::parse_with_format("5/20/25 2:30:45.123 PM", "%-m/%-d/%y %-I:%M:%S.{ms} %p").unwrap();
+/// assert_eq!(ts_ms, TimeStampMs::new(1747751445, 123));
+///
+/// assert!(time_format::parse_with_format("45.5 9", "%S.{ms} {ms}").is_err());
+/// ```
+pub fn parse_with_format(s: impl AsRef<str>, format: &str) -> Result<TimeStampMs, Error> {
+    parse::parse_with_format(s.as_ref(), format)
 }
@@ -0,0 +1,774 @@
+//! Built-in locale data for month and weekday names, AM/PM markers,
+//! default date and time patterns, eras and alternative numerals.
+
+use std::convert::TryFrom;
+
+use crate::{calendar, tm};
+
+/// Names and patterns used to render locale-dependent conversions
+/// (`%a`, `%A`, `%b`, `%B`, `%p`, `%c`, `%x`, `%X` and `%r`, and the `E`
+/// and `O` modifiers) without depending on the process-global C locale.
+///
+/// Built-in locales are available as associated constants, or by name with
+/// [`Locale::from_name`]. A custom locale can be defined by filling in all
+/// the fields. Its date and time patterns can't use `%c`, `%x`, `%X` or
+/// `%r`, which are rejected with [`Error::InvalidFormat`](crate::Error::InvalidFormat)
+/// when formatting.
+///
+/// ```rust
+/// use time_format::Locale;
+///
+/// let ts = 1747751445; // 2025-05-20T14:30:45Z
+///
+/// let fr = time_format::strftime_utc_locale("%A %d %B %Y", ts, &Locale::FR).unwrap();
+/// assert_eq!(fr, "mardi 20 mai 2025");
+///
+/// let de = Locale::from_name("de_DE.UTF-8").unwrap();
+/// assert_eq!(time_format::strftime_utc_locale("%x", ts, &de).unwrap(), "20.05.2025");
+/// ```
+///
+/// Locales with eras render them with the `E` modifier, and locales with
+/// alternative numerals use them with the `O` modifier. Other locales
+/// render these conversions as if there was no modifier.
+///
+/// ```rust
+/// use time_format::Locale;
+///
+/// let ts = 1747751445; // 2025-05-20T14:30:45Z
+///
+/// let ja = time_format::strftime_utc_locale("%EC %Ey, %Ex", ts, &Locale::JA).unwrap();
+/// assert_eq!(ja, "令和 7, 令和7年05月20日");
+///
+/// let first_year = time_format::strftime_utc_locale("%EY", 1556668800, &Locale::JA).unwrap();
+/// assert_eq!(first_year, "令和元年");
+///
+/// let zh = time_format::strftime_utc_locale("%Om月%Od日", ts, &Locale::ZH).unwrap();
+/// assert_eq!(zh, "五月二十日");
+///
+/// let fr = time_format::strftime_utc_locale("%EY %Od", ts, &Locale::FR).unwrap();
+/// assert_eq!(fr, "2025 20");
+/// ```
+///
+/// A custom locale based on a built-in one:
+///
+/// ```rust
+/// use time_format::{Error, FormatErrorReason, Locale};
+///
+/// let ts = 1747751445; // 2025-05-20T14:30:45Z
+///
+/// static ISO: Locale = Locale { date_time_format: "%F %T", ..Locale::EN };
+/// assert_eq!(time_format::strftime_utc_locale("%c", ts, &ISO).unwrap(), "2025-05-20 14:30:45");
+///
+/// static NESTED: Locale = Locale { date_time_format: "%x %X", ..Locale::EN };
+/// match time_format::strftime_utc_locale("%c", ts, &NESTED) {
+///     Err(Error::InvalidFormat(e)) => assert_eq!(e.reason, FormatErrorReason::NestedLocalePattern),
+///     _ => unreachable!(),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Locale {
+    /// Language code, e.g. `fr`.
+    pub name: &'static str,
+    /// Full month names, January first.
+    pub months: [&'static str; 12],
+    /// Abbreviated month names, January first.
+    pub months_abbr: [&'static str; 12],
+    /// Full weekday names, Sunday first.
+    pub weekdays: [&'static str; 7],
+    /// Abbreviated weekday names, Sunday first.
+    pub weekdays_abbr: [&'static str; 7],
+    /// Markers for times before and after noon.
+    pub am_pm: [&'static str; 2],
+    /// Date and time pattern, used for `%c`.
+    pub date_time_format: &'static str,
+    /// Date pattern, used for `%x`.
+    pub date_format: &'static str,
+    /// Time pattern, used for `%X`.
+    pub time_format: &'static str,
+    /// 12-hour time pattern, used for `%r`.
+    pub time_12h_format: &'static str,
+    /// Eras, most recent first, used for `%EC`, `%Ey` and `%EY`. Empty if
+    /// the locale has none.
+    pub eras: &'static [Era],
+    /// Date and time pattern with eras, used for `%Ec`.
+    pub era_date_time_format: &'static str,
+    /// Date pattern with eras, used for `%Ex`.
+    pub era_date_format: &'static str,
+    /// Time pattern with eras, used for `%EX`.
+    pub era_time_format: &'static str,
+    /// Alternative numerals for 0 to 99, used with the `O` modifier. Empty
+    /// if the locale has none.
+    pub alt_digits: &'static [&'static str],
+}
+
+/// An era of a locale's calendar, such as a Japanese imperial era.
+///
+/// Years of an era are counted from 1, starting with the Gregorian year of
+/// its first day.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Era {
+    /// First day of the era in the Gregorian calendar: year, month (1-12)
+    /// and day (1-31).
+    pub start: (i32, u32, u32),
+    /// Name of the era, used for `%EC`.
+    pub name: &'static str,
+    /// Text following the year of the era in `%EY`, e.g. `年`.
+    pub year_suffix: &'static str,
+    /// Replacement for the number of the first year of the era in `%EY`,
+    /// e.g. `元`.
+    pub first_year: Option<&'static str>,
+}
+
+/// Japanese imperial eras since the adoption of the Gregorian calendar.
+const JA_ERAS: [Era; 5] = [
+    Era {
+        start: (2019, 5, 1),
+        name: "令和",
+        year_suffix: "年",
+        first_year: Some("元"),
+    },
+    Era {
+        start: (1989, 1, 8),
+        name: "平成",
+        year_suffix: "年",
+        first_year: Some("元"),
+    },
+    Era {
+        start: (1926, 12, 25),
+        name: "昭和",
+        year_suffix: "年",
+        first_year: Some("元"),
+    },
+    Era {
+        start: (1912, 7, 30),
+        name: "大正",
+        year_suffix: "年",
+        first_year: Some("元"),
+    },
+    Era {
+        start: (1873, 1, 1),
+        name: "明治",
+        year_suffix: "年",
+        first_year: Some("元"),
+    },
+];
+
+/// Chinese and Japanese numerals for 0 to 99.
+const CJK_DIGITS: [&str; 100] = [
+    "〇",
+    "一",
+    "二",
+    "三",
+    "四",
+    "五",
+    "六",
+    "七",
+    "八",
+    "九",
+    "十",
+    "十一",
+    "十二",
+    "十三",
+    "十四",
+    "十五",
+    "十六",
+    "十七",
+    "十八",
+    "十九",
+    "二十",
+    "二十一",
+    "二十二",
+    "二十三",
+    "二十四",
+    "二十五",
+    "二十六",
+    "二十七",
+    "二十八",
+    "二十九",
+    "三十",
+    "三十一",
+    "三十二",
+    "三十三",
+    "三十四",
+    "三十五",
+    "三十六",
+    "三十七",
+    "三十八",
+    "三十九",
+    "四十",
+    "四十一",
+    "四十二",
+    "四十三",
+    "四十四",
+    "四十五",
+    "四十六",
+    "四十七",
+    "四十八",
+    "四十九",
+    "五十",
+    "五十一",
+    "五十二",
+    "五十三",
+    "五十四",
+    "五十五",
+    "五十六",
+    "五十七",
+    "五十八",
+    "五十九",
+    "六十",
+    "六十一",
+    "六十二",
+    "六十三",
+    "六十四",
+    "六十五",
+    "六十六",
+    "六十七",
+    "六十八",
+    "六十九",
+    "七十",
+    "七十一",
+    "七十二",
+    "七十三",
+    "七十四",
+    "七十五",
+    "七十六",
+    "七十七",
+    "七十八",
+    "七十九",
+    "八十",
+    "八十一",
+    "八十二",
+    "八十三",
+    "八十四",
+    "八十五",
+    "八十六",
+    "八十七",
+    "八十八",
+    "八十九",
+    "九十",
+    "九十一",
+    "九十二",
+    "九十三",
+    "九十四",
+    "九十五",
+    "九十六",
+    "九十七",
+    "九十八",
+    "九十九",
+];
+
+impl Locale {
+    /// English. The output matches the POSIX C locale.
+    pub const EN: Locale = Locale {
+        name: "en",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_abbr: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+        ],
+        weekdays: [
+            "Sunday",
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+        ],
+        weekdays_abbr: ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"],
+        am_pm: ["AM", "PM"],
+        date_time_format: "%a %b %e %H:%M:%S %Y",
+        date_format: "%m/%d/%y",
+        time_format: "%H:%M:%S",
+        time_12h_format: "%I:%M:%S %p",
+        eras: &[],
+        era_date_time_format: "%a %b %e %H:%M:%S %Y",
+        era_date_format: "%m/%d/%y",
+        era_time_format: "%H:%M:%S",
+        alt_digits: &[],
+    };
+
+    /// French.
+    pub const FR: Locale = Locale {
+        name: "fr",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_abbr: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.",
+            "nov.", "déc.",
+        ],
+        weekdays: [
+            "dimanche", "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi",
+        ],
+        weekdays_abbr: ["dim.", "lun.", "mar.", "mer.", "jeu.", "ven.", "sam."],
+        am_pm: ["AM", "PM"],
+        date_time_format: "%a %d %b %Y %H:%M:%S",
+        date_format: "%d/%m/%Y",
+        time_format: "%H:%M:%S",
+        time_12h_format: "%I:%M:%S %p",
+        eras: &[],
+        era_date_time_format: "%a %d %b %Y %H:%M:%S",
+        era_date_format: "%d/%m/%Y",
+        era_time_format: "%H:%M:%S",
+        alt_digits: &[],
+    };
+
+    /// German.
+    pub const DE: Locale = Locale {
+        name: "de",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_abbr: [
+            "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+        ],
+        weekdays: [
+            "Sonntag",
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+        ],
+        weekdays_abbr: ["So", "Mo", "Di", "Mi", "Do", "Fr", "Sa"],
+        am_pm: ["AM", "PM"],
+        date_time_format: "%a %d %b %Y %H:%M:%S",
+        date_format: "%d.%m.%Y",
+        time_format: "%H:%M:%S",
+        time_12h_format: "%I:%M:%S %p",
+        eras: &[],
+        era_date_time_format: "%a %d %b %Y %H:%M:%S",
+        era_date_format: "%d.%m.%Y",
+        era_time_format: "%H:%M:%S",
+        alt_digits: &[],
+    };
+
+    /// Spanish.
+    pub const ES: Locale = Locale {
+        name: "es",
+        months: [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ],
+        months_abbr: [
+            "ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sep", "oct", "nov", "dic",
+        ],
+        weekdays: [
+            "domingo",
+            "lunes",
+            "martes",
+            "miércoles",
+            "jueves",
+            "viernes",
+            "sábado",
+        ],
+        weekdays_abbr: ["dom", "lun", "mar", "mié", "jue", "vie", "sáb"],
+        am_pm: ["a. m.", "p. m."],
+        date_time_format: "%a %d %b %Y %H:%M:%S",
+        date_format: "%d/%m/%y",
+        time_format: "%H:%M:%S",
+        time_12h_format: "%I:%M:%S %p",
+        eras: &[],
+        era_date_time_format: "%a %d %b %Y %H:%M:%S",
+        era_date_format: "%d/%m/%y",
+        era_time_format: "%H:%M:%S",
+        alt_digits: &[],
+    };
+
+    /// Italian.
+    pub const IT: Locale = Locale {
+        name: "it",
+        months: [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ],
+        months_abbr: [
+            "gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic",
+        ],
+        weekdays: [
+            "domenica",
+            "lunedì",
+            "martedì",
+            "mercoledì",
+            "giovedì",
+            "venerdì",
+            "sabato",
+        ],
+        weekdays_abbr: ["dom", "lun", "mar", "mer", "gio", "ven", "sab"],
+        am_pm: ["AM", "PM"],
+        date_time_format: "%a %d %b %Y %H:%M:%S",
+        date_format: "%d/%m/%Y",
+        time_format: "%H:%M:%S",
+        time_12h_format: "%I:%M:%S %p",
+        eras: &[],
+        era_date_time_format: "%a %d %b %Y %H:%M:%S",
+        era_date_format: "%d/%m/%Y",
+        era_time_format: "%H:%M:%S",
+        alt_digits: &[],
+    };
+
+    /// Portuguese.
+    pub const PT: Locale = Locale {
+        name: "pt",
+        months: [
+            "janeiro",
+            "fevereiro",
+            "março",
+            "abril",
+            "maio",
+            "junho",
+            "julho",
+            "agosto",
+            "setembro",
+            "outubro",
+            "novembro",
+            "dezembro",
+        ],
+        months_abbr: [
+            "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        weekdays: [
+            "domingo",
+            "segunda-feira",
+            "terça-feira",
+            "quarta-feira",
+            "quinta-feira",
+            "sexta-feira",
+            "sábado",
+        ],
+        weekdays_abbr: ["dom", "seg", "ter", "qua", "qui", "sex", "sáb"],
+        am_pm: ["AM", "PM"],
+        date_time_format: "%a %d %b %Y %H:%M:%S",
+        date_format: "%d/%m/%Y",
+        time_format: "%H:%M:%S",
+        time_12h_format: "%I:%M:%S %p",
+        eras: &[],
+        era_date_time_format: "%a %d %b %Y %H:%M:%S",
+        era_date_format: "%d/%m/%Y",
+        era_time_format: "%H:%M:%S",
+        alt_digits: &[],
+    };
+
+    /// Dutch.
+    pub const NL: Locale = Locale {
+        name: "nl",
+        months: [
+            "januari",
+            "februari",
+            "maart",
+            "april",
+            "mei",
+            "juni",
+            "juli",
+            "augustus",
+            "september",
+            "oktober",
+            "november",
+            "december",
+        ],
+        months_abbr: [
+            "jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec",
+        ],
+        weekdays: [
+            "zondag",
+            "maandag",
+            "dinsdag",
+            "woensdag",
+            "donderdag",
+            "vrijdag",
+            "zaterdag",
+        ],
+        weekdays_abbr: ["zo", "ma", "di", "wo", "do", "vr", "za"],
+        am_pm: ["AM", "PM"],
+        date_time_format: "%a %d %b %Y %H:%M:%S",
+        date_format: "%d-%m-%Y",
+        time_format: "%H:%M:%S",
+        time_12h_format: "%I:%M:%S %p",
+        eras: &[],
+        era_date_time_format: "%a %d %b %Y %H:%M:%S",
+        era_date_format: "%d-%m-%Y",
+        era_time_format: "%H:%M:%S",
+        alt_digits: &[],
+    };
+
+    /// Japanese.
+    pub const JA: Locale = Locale {
+        name: "ja",
+        months: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        months_abbr: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        weekdays: [
+            "日曜日",
+            "月曜日",
+            "火曜日",
+            "水曜日",
+            "木曜日",
+            "金曜日",
+            "土曜日",
+        ],
+        weekdays_abbr: ["日", "月", "火", "水", "木", "金", "土"],
+        am_pm: ["午前", "午後"],
+        date_time_format: "%Y年%m月%d日 %H時%M分%S秒",
+        date_format: "%Y年%m月%d日",
+        time_format: "%H時%M分%S秒",
+        time_12h_format: "%p%I時%M分%S秒",
+        eras: &JA_ERAS,
+        era_date_time_format: "%EY%m月%d日 %H時%M分%S秒",
+        era_date_format: "%EY%m月%d日",
+        era_time_format: "%H時%M分%S秒",
+        alt_digits: &CJK_DIGITS,
+    };
+
+    /// Chinese (simplified).
+    pub const ZH: Locale = Locale {
+        name: "zh",
+        months: [
+            "一月",
+            "二月",
+            "三月",
+            "四月",
+            "五月",
+            "六月",
+            "七月",
+            "八月",
+            "九月",
+            "十月",
+            "十一月",
+            "十二月",
+        ],
+        months_abbr: [
+            "1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月",
+        ],
+        weekdays: [
+            "星期日",
+            "星期一",
+            "星期二",
+            "星期三",
+            "星期四",
+            "星期五",
+            "星期六",
+        ],
+        weekdays_abbr: ["周日", "周一", "周二", "周三", "周四", "周五", "周六"],
+        am_pm: ["上午", "下午"],
+        date_time_format: "%Y年%m月%d日 %A %H时%M分%S秒",
+        date_format: "%Y年%m月%d日",
+        time_format: "%H时%M分%S秒",
+        time_12h_format: "%p %I时%M分%S秒",
+        eras: &[],
+        era_date_time_format: "%Y年%m月%d日 %A %H时%M分%S秒",
+        era_date_format: "%Y年%m月%d日",
+        era_time_format: "%H时%M分%S秒",
+        alt_digits: &CJK_DIGITS,
+    };
+
+    /// All the built-in locales.
+    pub const BUILTIN: [Locale; 9] = [
+        Locale::EN,
+        Locale::FR,
+        Locale::DE,
+        Locale::ES,
+        Locale::IT,
+        Locale::PT,
+        Locale::NL,
+        Locale::JA,
+        Locale::ZH,
+    ];
+
+    /// Look up a built-in locale by name.
+    ///
+    /// The name can be a language code (`fr`), or a POSIX or BCP 47 locale
+    /// name such as `fr_FR.UTF-8` or `fr-CA`, in which case only the language
+    /// is taken into account. Returns `None` for unknown languages.
+    pub fn from_name(name: impl AsRef<str>) -> Option<Locale> {
+        let language = name.as_ref().split(['_', '-', '.', '@']).next()?;
+        Self::BUILTIN
+            .iter()
+            .find(|locale| locale.name.eq_ignore_ascii_case(language))
+            .copied()
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::EN
+    }
+}
+
+/// The rendering of a locale-dependent conversion.
+pub(crate) enum LocaleText {
+    /// A name or marker, used as is.
+    Name(&'static str),
+    /// A number, such as the year of an era.
+    Number(i32),
+    /// The name of an era followed by a year of that era.
+    EraYear(&'static Era, i32),
+    /// A pattern, to be formatted with the same locale.
+    Pattern(&'static str),
+}
+
+impl Locale {
+    /// Render a conversion from the locale data, or return `None` if it
+    /// doesn't depend on the locale.
+    pub(crate) fn conversion(
+        &self,
+        modifier: Option<char>,
+        conv: char,
+        tm: &tm,
+    ) -> Option<LocaleText> {
+        match modifier {
+            Some('E') => return self.era_conversion(conv, tm),
+            Some(_) => return self.alt_digits_conversion(conv, tm),
+            None => {}
+        }
+        let month = tm.tm_mon.rem_euclid(12) as usize;
+        let week_day = tm.tm_wday.rem_euclid(7) as usize;
+        let text = match conv {
+            'a' => LocaleText::Name(self.weekdays_abbr[week_day]),
+            'A' => LocaleText::Name(self.weekdays[week_day]),
+            'b' | 'h' => LocaleText::Name(self.months_abbr[month]),
+            'B' => LocaleText::Name(self.months[month]),
+            'p' | 'P' => LocaleText::Name(self.am_pm[usize::from(tm.tm_hour >= 12)]),
+            'c' => LocaleText::Pattern(self.date_time_format),
+            'x' => LocaleText::Pattern(self.date_format),
+            'X' => LocaleText::Pattern(self.time_format),
+            'r' => LocaleText::Pattern(self.time_12h_format),
+            _ => return None,
+        };
+        Some(text)
+    }
+
+    /// Render a conversion with the `E` modifier, falling back to the
+    /// conversion without modifier for dates before the first era.
+    fn era_conversion(&self, conv: char, tm: &tm) -> Option<LocaleText> {
+        let text = match conv {
+            'c' => LocaleText::Pattern(self.era_date_time_format),
+            'x' => LocaleText::Pattern(self.era_date_format),
+            'X' => LocaleText::Pattern(self.era_time_format),
+            _ => {
+                let year = tm.tm_year + 1900;
+                let date = (year, tm.tm_mon as u32 + 1, tm.tm_mday as u32);
+                let era = match self.eras.iter().find(|era| era.start <= date) {
+                    Some(era) => era,
+                    None => return self.conversion(None, conv, tm),
+                };
+                let era_year = year - era.start.0 + 1;
+                match conv {
+                    'C' => LocaleText::Name(era.name),
+                    'y' => LocaleText::Number(era_year),
+                    'Y' => LocaleText::EraYear(era, era_year),
+                    _ => return None,
+                }
+            }
+        };
+        Some(text)
+    }
+
+    /// Render a conversion with the `O` modifier using the alternative
+    /// numerals, if the locale has them.
+    fn alt_digits_conversion(&self, conv: char, tm: &tm) -> Option<LocaleText> {
+        let week_day = tm.tm_wday.rem_euclid(7);
+        let monday_week_day = (week_day + 6) % 7;
+        let number = match conv {
+            'd' | 'e' => tm.tm_mday,
+            'H' => tm.tm_hour,
+            'I' => (tm.tm_hour + 11) % 12 + 1,
+            'm' => tm.tm_mon + 1,
+            'M' => tm.tm_min,
+            'S' => tm.tm_sec,
+            'u' => monday_week_day + 1,
+            'w' => week_day,
+            'U' => (tm.tm_yday + 7 - week_day) / 7,
+            'W' => (tm.tm_yday + 7 - monday_week_day) / 7,
+            'V' => iso_week(tm),
+            'y' => (tm.tm_year + 1900).rem_euclid(100),
+            _ => return self.conversion(None, conv, tm),
+        };
+        usize::try_from(number)
+            .ok()
+            .and_then(|number| self.alt_digits.get(number))
+            .map(|digits| LocaleText::Name(digits))
+    }
+}
+
+/// ISO 8601 week number, from 1 to 53.
+fn iso_week(tm: &tm) -> i32 {
+    let monday_week_day = (tm.tm_wday.rem_euclid(7) + 6) % 7;
+    let week = (tm.tm_yday - monday_week_day + 10) / 7;
+    let year = i64::from(tm.tm_year) + 1900;
+    let weeks_in_year = |year: i64| {
+        // Years starting on a Thursday, and leap years starting on a
+        // Wednesday, have 53 weeks
+        let jan1 = (calendar::days_from_civil(year, 1, 1) + 3).rem_euclid(7);
+        if jan1 == 3 || (jan1 == 2 && calendar::is_leap_year(year)) {
+            53
+        } else {
+            52
+        }
+    };
+    if week < 1 {
+        weeks_in_year(year - 1)
+    } else if week > weeks_in_year(year) {
+        1
+    } else {
+        week
+    }
+}
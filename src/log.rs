@@ -0,0 +1,199 @@
+//! Timestamps for the `log` crate ecosystem, enabled with the `log` feature.
+//!
+//! [`LogTimestamp`] writes the current time as a log line prefix into any
+//! `io::Write`, such as the buffer given to `env_logger`'s format callback:
+//! `builder.format(|buf, record| TIMESTAMP.write_record(buf, record))`.
+//!
+//! The rendering of the date and time is cached per thread and reused
+//! while the second doesn't change, so that only the fractional part is
+//! formatted for most log lines.
+//!
+//! ```rust
+//! use log::{Log, Metadata, Record};
+//! use time_format::{log::LogTimestamp, DateFormat, Precision};
+//!
+//! static TIMESTAMP: LogTimestamp = LogTimestamp::utc(DateFormat::RFC3339);
+//!
+//! struct StderrLogger;
+//!
+//! impl Log for StderrLogger {
+//!     fn enabled(&self, _: &Metadata) -> bool {
+//!         true
+//!     }
+//!
+//!     fn log(&self, record: &Record) {
+//!         // "2025-05-20T14:30:45.123+00:00 INFO  app: started"
+//!         let _ = TIMESTAMP.write_record(&mut std::io::stderr(), record);
+//!     }
+//!
+//!     fn flush(&self) {}
+//! }
+//!
+//! let mut buf = Vec::new();
+//! let ts = time_format::TimeStampMs::new(1747751445, 123);
+//! LogTimestamp::local(DateFormat::SQL)
+//!     .with_precision(Precision::Seconds)
+//!     .write(&mut buf, ts)
+//!     .unwrap();
+//! LogTimestamp::utc(DateFormat::SQL).write(&mut buf, ts).unwrap();
+//! assert!(String::from_utf8(buf).unwrap().ends_with("2025-05-20 14:30:45.123"));
+//!
+//! let mut buf = Vec::new();
+//! LogTimestamp::utc(DateFormat::RFC5424)
+//!     .with_precision(Precision::Microseconds)
+//!     .write(&mut buf, time_format::TimeStampUs::new(1747751445, 123456))
+//!     .unwrap();
+//! assert_eq!(buf, b"2025-05-20T14:30:45.123456+00:00");
+//! ```
+
+use std::{convert::TryFrom, fmt, io, time::SystemTime};
+
+use crate::{cache::CachedFormat, DateFormat, Error, Precision, TimeStampUs, TimeZone};
+
+/// Writer of timestamps for log lines.
+///
+/// The timestamp is rendered with a [`DateFormat`], in UTC, in the local
+/// time zone or at a fixed offset. Fractional seconds are included if the precision isn't
+/// [`Precision::Seconds`] and the format can include them (see
+/// [`format_common_ms_utc`](crate::format_common_ms_utc)).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct LogTimestamp {
+    format: DateFormat,
+    zone: TimeZone,
+    precision: Precision,
+}
+
+impl Default for LogTimestamp {
+    /// RFC 3339 timestamps in UTC, with millisecond precision.
+    fn default() -> Self {
+        Self::utc(DateFormat::RFC3339)
+    }
+}
+
+fn to_io_error(err: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, err)
+}
+
+impl LogTimestamp {
+    /// Timestamps in UTC, with millisecond precision.
+    pub const fn utc(format: DateFormat) -> Self {
+        Self {
+            format,
+            zone: TimeZone::Utc,
+            precision: Precision::Milliseconds,
+        }
+    }
+
+    /// Timestamps in the local time zone, with millisecond precision.
+    pub const fn local(format: DateFormat) -> Self {
+        Self {
+            format,
+            zone: TimeZone::Local,
+            precision: Precision::Milliseconds,
+        }
+    }
+
+    /// Set the time zone. HTTP dates ignore it and always use UTC.
+    pub const fn with_zone(mut self, zone: TimeZone) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    /// Set the precision of the fractional seconds.
+    pub const fn with_precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Write the current time.
+    pub fn write_now<W: io::Write + ?Sized>(&self, w: &mut W) -> io::Result<()> {
+        let ts_us = TimeStampUs::try_from(SystemTime::now()).map_err(to_io_error)?;
+        self.write(w, ts_us)
+    }
+
+    /// Write a timestamp.
+    ///
+    /// The timestamp is a [`TimeStampUs`], or a
+    /// [`TimeStampMs`](crate::TimeStampMs), which has no digits past the
+    /// milliseconds.
+    ///
+    /// The writer may itself write timestamps, for example by logging:
+    ///
+    /// ```rust
+    /// use std::io::{self, Write};
+    /// use time_format::{log::LogTimestamp, DateFormat, TimeStampMs};
+    ///
+    /// static TIMESTAMP: LogTimestamp = LogTimestamp::utc(DateFormat::RFC3339);
+    ///
+    /// struct Traced(Vec<u8>);
+    ///
+    /// impl Write for Traced {
+    ///     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    ///         let mut trace = Vec::new();
+    ///         TIMESTAMP.write(&mut trace, TimeStampMs::new(0, 0))?;
+    ///         self.0.extend_from_slice(buf);
+    ///         Ok(buf.len())
+    ///     }
+    ///
+    ///     fn flush(&mut self) -> io::Result<()> {
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut w = Traced(Vec::new());
+    /// TIMESTAMP.write(&mut w, TimeStampMs::new(1747751445, 123)).unwrap();
+    /// assert_eq!(w.0, b"2025-05-20T14:30:45.123+00:00");
+    /// ```
+    pub fn write<W: io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        ts: impl Into<TimeStampUs>,
+    ) -> io::Result<()> {
+        let ts_us = ts.into();
+        let format = CachedFormat {
+            format: self.format.into(),
+            zone: self.zone,
+            precision: self.precision,
+        };
+        let mut out = IoWriter {
+            inner: w,
+            error: None,
+        };
+        format
+            .write(&mut out, ts_us.seconds, self.precision.fraction(ts_us))
+            .map_err(|err| out.error.take().unwrap_or_else(|| to_io_error(err)))
+    }
+
+    /// Write the current time, the level, the target and the message of a
+    /// log record, followed by a newline:
+    /// `2025-05-20T14:30:45.123+00:00 INFO  app: started`.
+    pub fn write_record<W: io::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        record: &::log::Record<'_>,
+    ) -> io::Result<()> {
+        self.write_now(w)?;
+        writeln!(
+            w,
+            " {:<5} {}: {}",
+            record.level(),
+            record.target(),
+            record.args()
+        )
+    }
+}
+
+/// Adapter writing formatted text into an `io::Write`, keeping its errors.
+struct IoWriter<'a, W: ?Sized> {
+    inner: &'a mut W,
+    error: Option<io::Error>,
+}
+
+impl<W: io::Write + ?Sized> fmt::Write for IoWriter<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.inner.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
@@ -0,0 +1,705 @@
+//! Parsing of dates and times: ISO 8601 / RFC 3339 and its basic format,
+//! HTTP dates, RFC 5424 syslog timestamps, and strftime-style formats for
+//! [`parse_with_format`](crate::parse_with_format).
+
+use std::convert::TryFrom;
+
+use crate::{
+    calendar,
+    strftime::{self, Token},
+    tm_in_zone, Error, Locale, TimeStamp, TimeStampMs, TimeStampUs, TimeZone,
+};
+
+/// Date and time fields of a parsed ISO 8601 string.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub(crate) struct DateTimeFields {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+    /// Offset from UTC in seconds, if specified.
+    pub offset: Option<i32>,
+}
+
+impl DateTimeFields {
+    /// Convert to a timestamp, treating a missing offset as UTC.
+    pub fn to_timestamp(self) -> Result<TimeStampMs, Error> {
+        self.to_timestamp_us().map(TimeStampUs::to_timestamp_ms)
+    }
+
+    /// Convert to a timestamp with microsecond precision, treating a
+    /// missing offset as UTC.
+    pub fn to_timestamp_us(self) -> Result<TimeStampUs, Error> {
+        if self.month < 1
+            || self.month > 12
+            || self.day < 1
+            || self.day > calendar::days_in_month(self.year, self.month)
+            || self.hour > 23
+            || self.minute > 59
+            || self.second > 60
+        {
+            return Err(Error::InvalidTimestamp);
+        }
+        let seconds = calendar::timestamp_from_civil(
+            self.year,
+            self.month,
+            self.day,
+            self.hour,
+            self.minute,
+            self.second,
+        ) - i64::from(self.offset.unwrap_or(0));
+        Ok(TimeStampUs::new(seconds, self.nanosecond / 1000))
+    }
+
+    /// Convert to a timestamp, treating a missing offset as the time zone.
+    pub fn to_timestamp_in(self, zone: TimeZone) -> Result<TimeStampMs, Error> {
+        let naive = self.to_timestamp()?;
+        if self.offset.is_some() {
+            return Ok(naive);
+        }
+        let offset = match zone {
+            TimeZone::Utc => 0,
+            TimeZone::FixedOffset(offset) => i64::from(offset),
+            TimeZone::Local => {
+                // The offset at the naive time, corrected once for the
+                // case where a transition lies between the two instants
+                let offset = local_offset(naive.seconds)?;
+                local_offset(naive.seconds - offset)?
+            }
+        };
+        Ok(TimeStampMs::new(naive.seconds - offset, naive.milliseconds))
+    }
+}
+
+fn local_offset(ts: TimeStamp) -> Result<i64, Error> {
+    // `c_long` is only 32 bits wide on some platforms
+    #[allow(clippy::useless_conversion)]
+    let offset = i64::from(tm_in_zone(ts, TimeZone::Local)?.tm_gmtoff);
+    Ok(offset)
+}
+
+/// Parse a run of exactly `len` ASCII digits at the start of `s`.
+pub(crate) fn digits(s: &str, len: usize) -> Result<(u32, &str), Error> {
+    match s.get(..len) {
+        Some(d) if d.bytes().all(|b| b.is_ascii_digit()) => {
+            Ok((d.parse().map_err(|_| Error::ParseError)?, &s[len..]))
+        }
+        _ => Err(Error::ParseError),
+    }
+}
+
+pub(crate) fn expect(s: &str, c: char) -> Result<&str, Error> {
+    s.strip_prefix(c).ok_or(Error::ParseError)
+}
+
+/// Parse a full date `YYYY-MM-DD`.
+pub(crate) fn parse_date(s: &str) -> Result<(i64, u32, u32, &str), Error> {
+    let (year, s) = digits(s, 4)?;
+    let (month, s) = digits(expect(s, '-')?, 2)?;
+    let (day, s) = digits(expect(s, '-')?, 2)?;
+    Ok((i64::from(year), month, day, s))
+}
+
+/// Parse a time `hh:mm[:ss[.fff]]`, returning hour, minute, second and nanoseconds.
+pub(crate) fn parse_time(s: &str) -> Result<(u32, u32, u32, u32, &str), Error> {
+    let (hour, s) = digits(s, 2)?;
+    let (minute, mut s) = digits(expect(s, ':')?, 2)?;
+    let (mut second, mut nanosecond) = (0, 0);
+    if let Some(rest) = s.strip_prefix(':') {
+        let (sec, rest) = digits(rest, 2)?;
+        second = sec;
+        s = rest;
+        if let Some(rest) = s.strip_prefix(['.', ',']) {
+            let len = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            if len == 0 {
+                return Err(Error::ParseError);
+            }
+            nanosecond = nanoseconds(&rest[..len]);
+            s = &rest[len..];
+        }
+    }
+    Ok((hour, minute, second, nanosecond, s))
+}
+
+/// Convert the digits of a fraction of a second to nanoseconds, ignoring
+/// digits past the ninth.
+fn nanoseconds(digits: &str) -> u32 {
+    digits
+        .bytes()
+        .take(9)
+        .zip([
+            100_000_000,
+            10_000_000,
+            1_000_000,
+            100_000,
+            10_000,
+            1_000,
+            100,
+            10,
+            1,
+        ])
+        .map(|(digit, scale)| u32::from(digit - b'0') * scale)
+        .sum()
+}
+
+/// Parse a UTC offset: `Z`, `±hh:mm`, `±hhmm` or `±hh`. Returns `None` if
+/// the input is empty.
+pub(crate) fn parse_offset(s: &str) -> Result<Option<i32>, Error> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    if s == "Z" || s == "z" {
+        return Ok(Some(0));
+    }
+    let sign = match s.as_bytes()[0] {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return Err(Error::ParseError),
+    };
+    let (hours, rest) = digits(&s[1..], 2)?;
+    let minutes = match rest {
+        "" => 0,
+        _ => {
+            let (minutes, rest) = digits(rest.strip_prefix(':').unwrap_or(rest), 2)?;
+            if !rest.is_empty() {
+                return Err(Error::ParseError);
+            }
+            minutes
+        }
+    };
+    if hours > 23 || minutes > 59 {
+        return Err(Error::ParseError);
+    }
+    let offset = i32::try_from(hours * 3600 + minutes * 60).map_err(|_| Error::ParseError)?;
+    Ok(Some(sign * offset))
+}
+
+/// Parse an ISO 8601 date, optionally followed by a time and a UTC offset,
+/// e.g. `2025-05-20`, `2025-05-20T14:30` or `2025-05-20T14:30:45.123+02:00`.
+pub(crate) fn parse_datetime(s: &str) -> Result<DateTimeFields, Error> {
+    let (year, month, day, rest) = parse_date(s)?;
+    let mut fields = DateTimeFields {
+        year,
+        month,
+        day,
+        ..Default::default()
+    };
+    if rest.is_empty() {
+        return Ok(fields);
+    }
+    let rest = rest
+        .strip_prefix(['T', 't', ' '])
+        .ok_or(Error::ParseError)?;
+    let (hour, minute, second, nanosecond, rest) = parse_time(rest)?;
+    fields.hour = hour;
+    fields.minute = minute;
+    fields.second = second;
+    fields.nanosecond = nanosecond;
+    fields.offset = parse_offset(rest)?;
+    Ok(fields)
+}
+
+/// Parse the end of an ISO 8601 interval, in which leading components that
+/// are the same as the start can be omitted, e.g. `12:00` or `21T12:00`
+/// after `2025-05-20T10:00`.
+pub(crate) fn parse_abbreviated_datetime(
+    s: &str,
+    start: &DateTimeFields,
+) -> Result<DateTimeFields, Error> {
+    let (date, time) = match s.find(['T', 't']) {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None if s.contains(':') => ("", Some(s)),
+        None => (s, None),
+    };
+
+    let mut fields = *start;
+    let parts: Vec<&str> = if date.is_empty() {
+        Vec::new()
+    } else {
+        date.split('-').collect()
+    };
+    match parts.as_slice() {
+        [] => {}
+        [day] => fields.day = digits(day, 2).and_then(full)?,
+        [month, day] => {
+            fields.month = digits(month, 2).and_then(full)?;
+            fields.day = digits(day, 2).and_then(full)?;
+        }
+        _ => {
+            let (year, month, day, rest) = parse_date(date)?;
+            if !rest.is_empty() {
+                return Err(Error::ParseError);
+            }
+            fields.year = year;
+            fields.month = month;
+            fields.day = day;
+        }
+    }
+
+    match time {
+        Some(time) => {
+            let (hour, minute, second, nanosecond, rest) = parse_time(time)?;
+            fields.hour = hour;
+            fields.minute = minute;
+            fields.second = second;
+            fields.nanosecond = nanosecond;
+            if let Some(offset) = parse_offset(rest)? {
+                fields.offset = Some(offset);
+            }
+        }
+        None => {
+            fields.hour = 0;
+            fields.minute = 0;
+            fields.second = 0;
+            fields.nanosecond = 0;
+        }
+    }
+    Ok(fields)
+}
+
+/// Check that a number spans the whole input.
+fn full((value, rest): (u32, &str)) -> Result<u32, Error> {
+    if rest.is_empty() {
+        Ok(value)
+    } else {
+        Err(Error::ParseError)
+    }
+}
+
+/// Parse an RFC 3339 date and time, which requires a time and a UTC offset:
+/// `2025-05-20T14:30:45Z` or `2025-05-20T14:30:45.123+02:00`.
+pub(crate) fn parse_rfc3339(s: &str) -> Result<TimeStampMs, Error> {
+    let fields = parse_datetime(s)?;
+    if fields.offset.is_none() || s.len() <= "YYYY-MM-DD".len() {
+        return Err(Error::ParseError);
+    }
+    fields.to_timestamp()
+}
+
+/// English month abbreviations, as used by HTTP and syslog dates.
+pub(crate) const MONTHS_ABBR: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// English weekday abbreviations, starting on Sunday.
+pub(crate) const WEEKDAYS_ABBR: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// Parse an English month abbreviation, returning the month number (1-12).
+pub(crate) fn parse_month_abbr(s: &str) -> Result<(u32, &str), Error> {
+    let name = s.get(..3).ok_or(Error::ParseError)?;
+    let month = MONTHS_ABBR
+        .iter()
+        .position(|m| m.eq_ignore_ascii_case(name))
+        .ok_or(Error::ParseError)?;
+    Ok((month as u32 + 1, &s[3..]))
+}
+
+/// Parse an HTTP date (RFC 7231 IMF-fixdate): `Tue, 20 May 2025 14:30:45 GMT`.
+pub(crate) fn parse_http_date(s: &str) -> Result<TimeStampMs, Error> {
+    let weekday = s.get(..3).ok_or(Error::ParseError)?;
+    if !WEEKDAYS_ABBR.contains(&weekday) {
+        return Err(Error::ParseError);
+    }
+    let s = s[3..].strip_prefix(", ").ok_or(Error::ParseError)?;
+    let (day, s) = digits(s, 2)?;
+    let (month, s) = parse_month_abbr(expect(s, ' ')?)?;
+    let (year, s) = digits(expect(s, ' ')?, 4)?;
+    let (hour, minute, second, nanosecond, s) = parse_time(expect(s, ' ')?)?;
+    if s != " GMT" || nanosecond != 0 {
+        return Err(Error::ParseError);
+    }
+    DateTimeFields {
+        year: i64::from(year),
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        nanosecond,
+        offset: Some(0),
+    }
+    .to_timestamp()
+}
+
+/// Fields collected while parsing with a strftime format.
+#[derive(Debug, Default)]
+struct Parsed {
+    fields: DateTimeFields,
+    year: Option<i64>,
+    month: Option<u32>,
+    day: Option<u32>,
+    day_of_year: Option<u32>,
+    hour12: Option<u32>,
+    pm: Option<bool>,
+    epoch: Option<i64>,
+    nanosecond: Option<u32>,
+}
+
+/// Parse a run of 1 to `max` ASCII digits, after optional spaces.
+fn number(s: &str, max: usize) -> Result<(u32, &str), Error> {
+    let s = s.trim_start_matches(' ');
+    let len = s.bytes().take(max).take_while(u8::is_ascii_digit).count();
+    if len == 0 {
+        return Err(Error::ParseError);
+    }
+    Ok((s[..len].parse().map_err(|_| Error::ParseError)?, &s[len..]))
+}
+
+/// Match the longest of `names` at the start of `s`, ignoring case,
+/// returning its index.
+fn name<'a>(s: &'a str, names: &[&str]) -> Result<(usize, &'a str), Error> {
+    names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| {
+            s.get(..name.len())
+                .map_or(false, |prefix| prefix.eq_ignore_ascii_case(name))
+        })
+        .max_by_key(|(_, name)| name.len())
+        .map(|(i, name)| (i, &s[name.len()..]))
+        .ok_or(Error::ParseError)
+}
+
+fn parse_tokens<'a>(
+    mut s: &'a str,
+    tokens: &[Token<'_>],
+    parsed: &mut Parsed,
+) -> Result<&'a str, Error> {
+    let en = &Locale::EN;
+    for token in tokens {
+        s = match *token {
+            Token::Text(text) => {
+                let mut s = s;
+                for c in text.chars() {
+                    s = if c.is_whitespace() {
+                        s.trim_start()
+                    } else {
+                        s.strip_prefix(c).ok_or(Error::ParseError)?
+                    };
+                }
+                s
+            }
+            Token::Millis => {
+                // Any number of digits, truncated to nanoseconds
+                let len = s.bytes().take_while(u8::is_ascii_digit).count();
+                if len == 0 || parsed.nanosecond.is_some() {
+                    return Err(Error::ParseError);
+                }
+                parsed.nanosecond = Some(nanoseconds(&s[..len]));
+                &s[len..]
+            }
+            Token::Spec(spec) => {
+                let composite = match spec.conv {
+                    'D' => Some("%m/%d/%y"),
+                    'F' => Some("%Y-%m-%d"),
+                    'R' => Some("%H:%M"),
+                    'T' => Some("%H:%M:%S"),
+                    'r' => Some("%I:%M:%S %p"),
+                    _ => None,
+                };
+                if let Some(composite) = composite {
+                    let tokens = strftime::parse(composite)?;
+                    s = parse_tokens(s, &tokens, parsed)?;
+                    continue;
+                }
+                match spec.conv {
+                    'Y' => {
+                        let (year, rest) = number(s, 4)?;
+                        parsed.year = Some(i64::from(year));
+                        rest
+                    }
+                    'y' => {
+                        // POSIX: 69-99 are in the 20th century, 00-68 in the 21st
+                        let (year, rest) = number(s, 2)?;
+                        let century = if year >= 69 { 1900 } else { 2000 };
+                        parsed.year = Some(century + i64::from(year));
+                        rest
+                    }
+                    'm' => {
+                        let (month, rest) = number(s, 2)?;
+                        parsed.month = Some(month);
+                        rest
+                    }
+                    'b' | 'B' | 'h' => {
+                        let mut names = en.months.to_vec();
+                        names.extend_from_slice(&en.months_abbr);
+                        let (i, rest) = name(s, &names)?;
+                        parsed.month = Some(i as u32 % 12 + 1);
+                        rest
+                    }
+                    'a' | 'A' => {
+                        let mut names = en.weekdays.to_vec();
+                        names.extend_from_slice(&en.weekdays_abbr);
+                        name(s, &names)?.1
+                    }
+                    'd' | 'e' => {
+                        let (day, rest) = number(s, 2)?;
+                        parsed.day = Some(day);
+                        rest
+                    }
+                    'j' => {
+                        let (day, rest) = number(s, 3)?;
+                        parsed.day_of_year = Some(day);
+                        rest
+                    }
+                    'H' | 'k' => {
+                        let (hour, rest) = number(s, 2)?;
+                        parsed.fields.hour = hour;
+                        rest
+                    }
+                    'I' | 'l' => {
+                        let (hour, rest) = number(s, 2)?;
+                        parsed.hour12 = Some(hour);
+                        rest
+                    }
+                    'p' | 'P' => {
+                        let (i, rest) = name(s, &en.am_pm)?;
+                        parsed.pm = Some(i == 1);
+                        rest
+                    }
+                    'M' => {
+                        let (minute, rest) = number(s, 2)?;
+                        parsed.fields.minute = minute;
+                        rest
+                    }
+                    'S' => {
+                        let (second, rest) = number(s, 2)?;
+                        parsed.fields.second = second;
+                        rest
+                    }
+                    's' => {
+                        let (negative, digits) = match s.strip_prefix('-') {
+                            Some(digits) => (true, digits),
+                            None => (false, s),
+                        };
+                        let len = digits.bytes().take_while(u8::is_ascii_digit).count();
+                        let value: i64 = digits[..len].parse().map_err(|_| Error::ParseError)?;
+                        parsed.epoch = Some(if negative { -value } else { value });
+                        &digits[len..]
+                    }
+                    'z' => {
+                        let len = match s.as_bytes().first() {
+                            Some(b'Z') | Some(b'z') => 1,
+                            Some(b'+') | Some(b'-') => {
+                                1 + s[1..]
+                                    .bytes()
+                                    .take(5)
+                                    .take_while(|b| b.is_ascii_digit() || *b == b':')
+                                    .count()
+                            }
+                            _ => return Err(Error::ParseError),
+                        };
+                        parsed.fields.offset = parse_offset(&s[..len])?;
+                        &s[len..]
+                    }
+                    'Z' => {
+                        let (_, rest) = name(s, &["UTC", "GMT", "Z"])?;
+                        parsed.fields.offset = Some(0);
+                        rest
+                    }
+                    'n' | 't' => s.trim_start(),
+                    '%' => expect(s, '%')?,
+                    _ => return Err(Error::InvalidFormatString),
+                }
+            }
+        };
+    }
+    Ok(s)
+}
+
+/// Parse a date and time with a strftime format.
+pub(crate) fn parse_with_format(s: &str, format: &str) -> Result<TimeStampMs, Error> {
+    parse_with_format_offset(s, format).map(|(ts_ms, _)| ts_ms)
+}
+
+/// Parse a date and time with a strftime format, also returning the UTC
+/// offset if there was one.
+pub(crate) fn parse_with_format_offset(
+    s: &str,
+    format: &str,
+) -> Result<(TimeStampMs, Option<i32>), Error> {
+    let tokens = strftime::parse(format)?;
+    let mut parsed = Parsed::default();
+    let rest = parse_tokens(s, &tokens, &mut parsed)?;
+    if !rest.is_empty() {
+        return Err(Error::ParseError);
+    }
+    parsed.fields.nanosecond = parsed.nanosecond.unwrap_or(0);
+    let offset = parsed.fields.offset;
+    if let Some(epoch) = parsed.epoch {
+        let ts_ms = TimeStampMs::new(epoch, (parsed.fields.nanosecond / 1_000_000) as u16);
+        return Ok((ts_ms, offset));
+    }
+    let mut fields = parsed.fields;
+    fields.year = parsed.year.unwrap_or(1970);
+    match (parsed.day_of_year, parsed.month, parsed.day) {
+        (Some(day_of_year), None, None) => {
+            let days = calendar::days_from_civil(fields.year, 1, 1) + i64::from(day_of_year) - 1;
+            let (year, month, day) = calendar::civil_from_days(days);
+            if day_of_year == 0 || year != fields.year {
+                return Err(Error::InvalidTimestamp);
+            }
+            fields.month = month;
+            fields.day = day;
+        }
+        (_, month, day) => {
+            fields.month = month.unwrap_or(1);
+            fields.day = day.unwrap_or(1);
+        }
+    }
+    if let Some(hour) = parsed.hour12 {
+        if !(1..=12).contains(&hour) {
+            return Err(Error::InvalidTimestamp);
+        }
+        fields.hour = hour % 12 + if parsed.pm == Some(true) { 12 } else { 0 };
+    }
+    Ok((fields.to_timestamp()?, offset))
+}
+
+/// Year of a syslog (RFC 3164) date, which has none, relative to a
+/// reference time: the year of the reference, unless that would put the
+/// date more than a month after it, in which case it is the previous year.
+pub(crate) fn syslog_year(month: u32, day: u32, reference: i64) -> i64 {
+    let (year, _, _) = calendar::civil_from_days(reference.div_euclid(calendar::SECS_PER_DAY));
+    let day = day.min(calendar::days_in_month(year, month));
+    let date = calendar::days_from_civil(year, month, day) * calendar::SECS_PER_DAY;
+    if date > reference + 31 * calendar::SECS_PER_DAY {
+        year - 1
+    } else {
+        year
+    }
+}
+
+/// Parse a syslog (RFC 3164) date at the start of `s`: `May 20 14:30:45`,
+/// with a space-padded day, and optionally fractional seconds. The year is
+/// inferred from the reference time, as [`syslog_year`] does.
+pub(crate) fn parse_rfc3164_prefix(
+    s: &str,
+    reference: i64,
+) -> Result<(DateTimeFields, &str), Error> {
+    let (month, rest) = parse_month_abbr(s)?;
+    let rest = expect(rest, ' ')?;
+    // The day is padded with a space
+    let rest = rest.strip_prefix(' ').unwrap_or(rest);
+    let day_len = rest.bytes().take(2).take_while(u8::is_ascii_digit).count();
+    if day_len == 0 {
+        return Err(Error::ParseError);
+    }
+    let day: u32 = rest[..day_len].parse().map_err(|_| Error::ParseError)?;
+    let rest = expect(&rest[day_len..], ' ')?;
+    let (hour, minute, second, nanosecond, rest) = parse_time(rest)?;
+    let fields = DateTimeFields {
+        year: syslog_year(month, day, reference),
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        nanosecond,
+        offset: None,
+    };
+    Ok((fields, rest))
+}
+
+/// Parse a syslog (RFC 5424) timestamp: `2025-05-20T14:30:45.123456+02:00`,
+/// with at most six digits of fractional seconds, and `Z` or an offset
+/// with a colon.
+pub(crate) fn parse_rfc5424(s: &str) -> Result<TimeStampUs, Error> {
+    let (year, month, day, s) = parse_date(s)?;
+    let (hour, s) = digits(expect(s, 'T')?, 2)?;
+    let (minute, s) = digits(expect(s, ':')?, 2)?;
+    let (second, mut s) = digits(expect(s, ':')?, 2)?;
+    let mut nanosecond = 0;
+    if let Some(rest) = s.strip_prefix('.') {
+        let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if !(1..=6).contains(&len) {
+            return Err(Error::ParseError);
+        }
+        nanosecond = nanoseconds(&rest[..len]);
+        s = &rest[len..];
+    }
+    if s != "Z" && !(s.len() == 6 && s.as_bytes()[3] == b':') {
+        return Err(Error::ParseError);
+    }
+    DateTimeFields {
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        nanosecond,
+        offset: parse_offset(s)?,
+    }
+    .to_timestamp_us()
+}
+
+/// Parse a Common Log Format timestamp: `20/May/2025:14:30:45 -0500`,
+/// optionally in square brackets. Returns the timestamp and the offset.
+pub(crate) fn parse_common_log(s: &str) -> Result<(TimeStampMs, i32), Error> {
+    let s = match s.strip_prefix('[') {
+        Some(inner) => inner.strip_suffix(']').ok_or(Error::ParseError)?,
+        None => s,
+    };
+    let (day, s) = digits(s, 2)?;
+    let (month, s) = parse_month_abbr(expect(s, '/')?)?;
+    let (year, s) = digits(expect(s, '/')?, 4)?;
+    let (hour, s) = digits(expect(s, ':')?, 2)?;
+    let (minute, s) = digits(expect(s, ':')?, 2)?;
+    let (second, s) = digits(expect(s, ':')?, 2)?;
+    let s = expect(s, ' ')?;
+    if s.len() != 5 {
+        return Err(Error::ParseError);
+    }
+    let offset = parse_offset(s)?.ok_or(Error::ParseError)?;
+    let fields = DateTimeFields {
+        year: i64::from(year),
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        nanosecond: 0,
+        offset: Some(offset),
+    };
+    Ok((fields.to_timestamp()?, offset))
+}
+
+/// Parse an ISO 8601 date and time in basic format, with a time and an
+/// offset: `20250520T143045Z` or `20250520T143045.123-0500`.
+pub(crate) fn parse_iso8601_basic(s: &str) -> Result<TimeStampUs, Error> {
+    let (year, s) = digits(s, 4)?;
+    let (month, s) = digits(s, 2)?;
+    let (day, s) = digits(s, 2)?;
+    let (hour, s) = digits(expect(s, 'T')?, 2)?;
+    let (minute, s) = digits(s, 2)?;
+    let (second, mut s) = digits(s, 2)?;
+    let mut nanosecond = 0;
+    if let Some(rest) = s.strip_prefix(['.', ',']) {
+        let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return Err(Error::ParseError);
+        }
+        nanosecond = nanoseconds(&rest[..len]);
+        s = &rest[len..];
+    }
+    if s.contains(':') {
+        return Err(Error::ParseError);
+    }
+    DateTimeFields {
+        year: i64::from(year),
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        nanosecond,
+        offset: Some(parse_offset(s)?.ok_or(Error::ParseError)?),
+    }
+    .to_timestamp_us()
+}
@@ -0,0 +1,1158 @@
+//! Translation between strftime format strings and the date patterns of
+//! other languages and libraries.
+
+use crate::{
+    strftime::{self, Token},
+    Error,
+};
+
+/// A date pattern language other than strftime.
+///
+/// Exact translations to and from strftime give the same pattern back:
+///
+/// ```rust
+/// use time_format::PatternLanguage;
+///
+/// let format = "%a, %d %b %Y at %H:%M:%S.{ms} %:z";
+/// let patterns = [
+///     (PatternLanguage::Java, "EEE, dd MMM yyyy' at 'HH:mm:ss.SSS xxx"),
+///     (PatternLanguage::Moment, "ddd, DD MMM YYYY[ at ]HH:mm:ss.SSS Z"),
+///     (PatternLanguage::Go, "Mon, 02 Jan 2006 at 15:04:05.000 -07:00"),
+///     (PatternLanguage::DotNet, "ddd, dd MMM yyyy \\a\\t HH:mm:ss.fff zzz"),
+/// ];
+/// for (language, pattern) in patterns {
+///     let translation = language.from_strftime(format).unwrap();
+///     assert_eq!(translation.pattern, pattern);
+///     assert!(translation.is_exact());
+///     assert_eq!(language.to_strftime(pattern).pattern, format);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PatternLanguage {
+    /// Java `DateTimeFormatter`: `yyyy-MM-dd'T'HH:mm:ss.SSSXXX`
+    Java,
+    /// JavaScript moment.js and Day.js: `YYYY-MM-DD[T]HH:mm:ss.SSSZ`
+    Moment,
+    /// Go reference layouts: `2006-01-02T15:04:05.000Z07:00`
+    Go,
+    /// .NET custom date and time format strings: `yyyy-MM-dd'T'HH:mm:ss.fffzzz`
+    DotNet,
+}
+
+/// The result of a translation between pattern languages.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Translation {
+    /// The translated pattern.
+    pub pattern: String,
+    /// The constructs of the source pattern without an exact equivalent.
+    pub untranslatable: Vec<Untranslatable>,
+}
+
+impl Translation {
+    /// Whether every construct of the source pattern was translated exactly.
+    pub fn is_exact(&self) -> bool {
+        self.untranslatable.is_empty()
+    }
+}
+
+/// A construct of a pattern without an exact equivalent in the target
+/// language.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Untranslatable {
+    /// Byte offset of the construct in the source pattern.
+    pub position: usize,
+    /// The construct, as written in the source pattern.
+    pub construct: String,
+    /// What the translation contains instead, or `None` if the construct
+    /// was left out.
+    pub approximation: Option<String>,
+}
+
+/// Padding of a numeric field.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Pad {
+    Zero,
+    None,
+    Space,
+}
+
+/// A date or time field, in the terms of strftime.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Field {
+    /// Year with at least four digits
+    Year,
+    /// Last two digits of the year
+    Year2,
+    Month(Pad),
+    MonthAbbr,
+    MonthName,
+    Day(Pad),
+    DayOfYear(Pad),
+    WeekdayAbbr,
+    WeekdayName,
+    /// Day of the week, 1 for Monday to 7 for Sunday
+    WeekdayIso,
+    /// Day of the week, 0 for Sunday to 6 for Saturday
+    WeekdayFromSunday,
+    Hour(Pad),
+    Hour12(Pad),
+    Minute(Pad),
+    Second(Pad),
+    /// Three digits of fractional seconds
+    Millis,
+    /// `AM` or `PM`
+    AmPm,
+    /// `am` or `pm`
+    AmPmLower,
+    /// `+hhmm`
+    Offset,
+    /// `+hh:mm`
+    OffsetColon,
+    /// Time zone abbreviation
+    ZoneName,
+    /// Seconds since the epoch
+    Epoch,
+    IsoWeek,
+    IsoYear,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Item {
+    Literal(String),
+    Field(Field),
+}
+
+/// A construct of a source pattern, and its meaning.
+#[derive(Debug, Clone, PartialEq)]
+struct Piece {
+    position: usize,
+    construct: String,
+    /// The items it stands for, empty if there's no equivalent.
+    items: Vec<Item>,
+    exact: bool,
+}
+
+impl Piece {
+    fn literal(position: usize, text: &str) -> Self {
+        Self {
+            position,
+            construct: text.to_string(),
+            items: vec![Item::Literal(text.to_string())],
+            exact: true,
+        }
+    }
+
+    fn field(position: usize, construct: &str, field: Option<Field>, exact: bool) -> Self {
+        Self {
+            position,
+            construct: construct.to_string(),
+            exact: exact && field.is_some(),
+            items: field.map(Item::Field).into_iter().collect(),
+        }
+    }
+}
+
+impl PatternLanguage {
+    /// Translate a pattern of this language into a strftime format string,
+    /// with the `{ms}` placeholder for milliseconds.
+    ///
+    /// ```rust
+    /// use time_format::PatternLanguage;
+    ///
+    /// let java = PatternLanguage::Java.to_strftime("yyyy-MM-dd'T'HH:mm:ss.SSSxxx");
+    /// assert_eq!(java.pattern, "%Y-%m-%dT%H:%M:%S.{ms}%:z");
+    /// assert!(java.is_exact());
+    ///
+    /// let moment = PatternLanguage::Moment.to_strftime("ddd, D MMM YYYY [at] h:mm A");
+    /// assert_eq!(moment.pattern, "%a, %-d %b %Y at %-I:%M %p");
+    ///
+    /// let go = PatternLanguage::Go.to_strftime("2006-01-02T15:04:05.000Z07:00");
+    /// assert_eq!(go.pattern, "%Y-%m-%dT%H:%M:%S.{ms}%:z");
+    /// // Go writes `Z` instead of `+00:00` in UTC, strftime can't
+    /// assert_eq!(go.untranslatable[0].construct, "Z07:00");
+    ///
+    /// let dotnet = PatternLanguage::DotNet.to_strftime("dddd, MMMM d, yyyy g");
+    /// assert_eq!(dotnet.pattern, "%A, %B %-d, %Y ");
+    /// assert_eq!(dotnet.untranslatable[0].construct, "g");
+    /// assert_eq!(dotnet.untranslatable[0].approximation, None);
+    /// ```
+    ///
+    /// Quoted and escaped text is copied, with `%`, `{` and `}` escaped as
+    /// strftime requires:
+    ///
+    /// ```rust
+    /// use time_format::PatternLanguage;
+    ///
+    /// // Java quotes text with `'`, and writes a quote as `''`
+    /// let java = PatternLanguage::Java.to_strftime("h 'o''clock' a, '100%' ''yy");
+    /// assert_eq!(java.pattern, "%-I o'clock %p, 100%% '%y");
+    ///
+    /// // moment.js puts text in brackets, which can contain `]` but not `[`
+    /// let moment = PatternLanguage::Moment.to_strftime("[Today is] dddd [{ms}] [[x]]");
+    /// assert_eq!(moment.pattern, "Today is %A {{ms}} [x]");
+    ///
+    /// // .NET escapes single characters with `\`, and quotes text with `'` or `"`
+    /// let dotnet = PatternLanguage::DotNet.to_strftime("HH\\h mm\\m \"at\" 'o''clock' 100\\%");
+    /// assert_eq!(dotnet.pattern, "%Hh %Mm at oclock 100%%");
+    /// ```
+    pub fn to_strftime(self, pattern: &str) -> Translation {
+        let pieces = match self {
+            PatternLanguage::Java => parse_java(pattern),
+            PatternLanguage::Moment => parse_moment(pattern),
+            PatternLanguage::Go => parse_go(pattern),
+            PatternLanguage::DotNet => parse_dotnet(pattern),
+        };
+        render(&pieces, None)
+    }
+
+    /// Translate a strftime format string, with the `{ms}` placeholder for
+    /// milliseconds, into a pattern of this language.
+    ///
+    /// Locale-dependent directives such as `%c` have no equivalent, and
+    /// neither do case flags and field widths.
+    ///
+    /// ```rust
+    /// use time_format::PatternLanguage;
+    ///
+    /// let format = "%Y-%m-%dT%H:%M:%S.{ms}%:z";
+    /// assert_eq!(PatternLanguage::Java.from_strftime(format).unwrap().pattern, "yyyy-MM-dd'T'HH:mm:ss.SSSxxx");
+    /// assert_eq!(PatternLanguage::Moment.from_strftime(format).unwrap().pattern, "YYYY-MM-DD[T]HH:mm:ss.SSSZ");
+    /// assert_eq!(PatternLanguage::Go.from_strftime(format).unwrap().pattern, "2006-01-02T15:04:05.000-07:00");
+    /// assert_eq!(PatternLanguage::DotNet.from_strftime(format).unwrap().pattern, "yyyy-MM-dd\\THH:mm:ss.fffzzz");
+    ///
+    /// let go = PatternLanguage::Go.from_strftime("%d/%m/%Y (week %V)").unwrap();
+    /// assert_eq!(go.pattern, "02/01/2006 (week )");
+    /// assert_eq!(go.untranslatable[0].construct, "%V");
+    /// ```
+    ///
+    /// Text is escaped as the language requires, and `%%`, `{{` and `}}`
+    /// stand for `%`, `{` and `}`:
+    ///
+    /// ```rust
+    /// use time_format::PatternLanguage;
+    ///
+    /// let format = "%H:%M o'clock, 100%% {{sic}}";
+    /// let java = PatternLanguage::Java.from_strftime(format).unwrap();
+    /// assert_eq!(java.pattern, "HH:mm' o''clock, 100% {sic}'");
+    /// let moment = PatternLanguage::Moment.from_strftime(format).unwrap();
+    /// assert_eq!(moment.pattern, "HH:mm[ o'clock, 100% {sic}]");
+    /// let dotnet = PatternLanguage::DotNet.from_strftime(format).unwrap();
+    /// assert_eq!(dotnet.pattern, "HH:mm \\o\\'\\c\\l\\o\\c\\k, 100\\% {\\s\\i\\c}");
+    ///
+    /// // Go has no escapes: text that reads as a layout element is reported
+    /// let go = PatternLanguage::Go.from_strftime("%H:%M on day 1").unwrap();
+    /// assert_eq!(go.pattern, "15:04 on day 1");
+    /// assert_eq!(go.untranslatable[0].construct, " on day 1");
+    ///
+    /// // moment.js escapes `[` with a backslash, which Day.js doesn't support
+    /// let moment = PatternLanguage::Moment.from_strftime("[%Y]").unwrap();
+    /// assert_eq!(moment.pattern, "\\[YYYY[]]");
+    /// assert_eq!(moment.untranslatable.len(), 2);
+    ///
+    /// // Repeated letters are separated
+    /// let java = PatternLanguage::Java.from_strftime("%H%M%S%d%d").unwrap();
+    /// assert_eq!(java.pattern, "HHmmssdd[]dd");
+    /// ```
+    pub fn from_strftime(self, format: &str) -> Result<Translation, Error> {
+        let pieces = parse_strftime(format)?;
+        Ok(render(&pieces, Some(self)))
+    }
+}
+
+/// Render pieces into a pattern of a language, or into strftime.
+fn render(pieces: &[Piece], language: Option<PatternLanguage>) -> Translation {
+    let mut output = Output {
+        language,
+        pattern: String::new(),
+        untranslatable: Vec::new(),
+        literal: String::new(),
+        literal_position: 0,
+    };
+    for piece in pieces {
+        let mut exact = piece.exact;
+        if !exact
+            || piece
+                .items
+                .iter()
+                .any(|item| matches!(item, Item::Field(_)))
+        {
+            output.flush_literal();
+        }
+        let start = output.pattern.len();
+        for item in &piece.items {
+            match item {
+                Item::Literal(text) => output.push_literal(piece.position, text),
+                Item::Field(field) => {
+                    output.flush_literal();
+                    let (rendered, field_exact) = match language {
+                        None => (Some(strftime_field(*field)), true),
+                        Some(language) => language.field(*field, &output.pattern),
+                    };
+                    match rendered {
+                        Some(rendered) => output.pattern.push_str(&rendered),
+                        None => exact = false,
+                    }
+                    exact &= field_exact;
+                }
+            }
+        }
+        if !exact {
+            output.flush_literal();
+            let emitted = &output.pattern[start..];
+            let approximation = if emitted.is_empty() {
+                None
+            } else {
+                Some(emitted.to_string())
+            };
+            output.untranslatable.push(Untranslatable {
+                position: piece.position,
+                construct: piece.construct.clone(),
+                approximation,
+            });
+        }
+    }
+    output.flush_literal();
+    Translation {
+        pattern: output.pattern,
+        untranslatable: output.untranslatable,
+    }
+}
+
+/// A pattern being rendered, with the literal text not written yet.
+struct Output {
+    language: Option<PatternLanguage>,
+    pattern: String,
+    untranslatable: Vec<Untranslatable>,
+    literal: String,
+    literal_position: usize,
+}
+
+impl Output {
+    fn push_literal(&mut self, position: usize, text: &str) {
+        if self.literal.is_empty() {
+            self.literal_position = position;
+        }
+        self.literal.push_str(text);
+    }
+
+    /// Write the pending literal text, escaped for the language.
+    fn flush_literal(&mut self) {
+        if self.literal.is_empty() {
+            return;
+        }
+        let literal = &self.literal;
+        let pattern = &mut self.pattern;
+        match self.language {
+            None => {
+                for c in literal.chars() {
+                    match c {
+                        '%' => pattern.push_str("%%"),
+                        '{' => pattern.push_str("{{"),
+                        '}' => pattern.push_str("}}"),
+                        c => pattern.push(c),
+                    }
+                }
+            }
+            Some(PatternLanguage::Java) => {
+                if literal.contains(|c: char| c.is_ascii_alphabetic() || "'[]{}#".contains(c)) {
+                    pattern.push('\'');
+                    pattern.push_str(&literal.replace('\'', "''"));
+                    pattern.push('\'');
+                } else {
+                    pattern.push_str(literal);
+                }
+            }
+            Some(PatternLanguage::Moment) => {
+                let start = pattern.len();
+                // Text in brackets can contain `]` but not `[`, which is
+                // escaped with a backslash between bracketed parts
+                for (i, part) in literal.split('[').enumerate() {
+                    if i > 0 {
+                        pattern.push_str("\\[");
+                    }
+                    if part.contains(|c: char| c.is_ascii_alphabetic() || "]\\".contains(c)) {
+                        pattern.push('[');
+                        pattern.push_str(part);
+                        pattern.push(']');
+                    } else {
+                        pattern.push_str(part);
+                    }
+                }
+                // Day.js reads brackets and backslashes differently
+                if literal.contains(['[', ']', '\\']) {
+                    self.untranslatable.push(Untranslatable {
+                        position: self.literal_position,
+                        construct: literal.clone(),
+                        approximation: Some(pattern[start..].to_string()),
+                    });
+                }
+            }
+            Some(PatternLanguage::Go) => {
+                // Go has no escapes: text that reads as a layout element
+                // can't be written literally
+                let ambiguous = parse_go(literal)
+                    .iter()
+                    .any(|piece| !matches!(piece.items.as_slice(), [Item::Literal(_)]));
+                if ambiguous {
+                    self.untranslatable.push(Untranslatable {
+                        position: self.literal_position,
+                        construct: literal.clone(),
+                        approximation: Some(literal.clone()),
+                    });
+                }
+                pattern.push_str(literal);
+            }
+            Some(PatternLanguage::DotNet) => {
+                for c in literal.chars() {
+                    if c.is_ascii_alphabetic() || "'\"\\%".contains(c) {
+                        pattern.push('\\');
+                    }
+                    pattern.push(c);
+                }
+            }
+        }
+        self.literal.clear();
+    }
+}
+
+fn pad_flag(pad: Pad) -> &'static str {
+    match pad {
+        Pad::Zero => "",
+        Pad::None => "-",
+        Pad::Space => "_",
+    }
+}
+
+fn strftime_field(field: Field) -> String {
+    let numeric = |pad: Pad, conv: char| format!("%{}{}", pad_flag(pad), conv);
+    match field {
+        Field::Year => "%Y".to_string(),
+        Field::Year2 => "%y".to_string(),
+        Field::Month(pad) => numeric(pad, 'm'),
+        Field::MonthAbbr => "%b".to_string(),
+        Field::MonthName => "%B".to_string(),
+        Field::Day(Pad::Space) => "%e".to_string(),
+        Field::Day(pad) => numeric(pad, 'd'),
+        Field::DayOfYear(pad) => numeric(pad, 'j'),
+        Field::WeekdayAbbr => "%a".to_string(),
+        Field::WeekdayName => "%A".to_string(),
+        Field::WeekdayIso => "%u".to_string(),
+        Field::WeekdayFromSunday => "%w".to_string(),
+        Field::Hour(pad) => numeric(pad, 'H'),
+        Field::Hour12(pad) => numeric(pad, 'I'),
+        Field::Minute(pad) => numeric(pad, 'M'),
+        Field::Second(pad) => numeric(pad, 'S'),
+        Field::Millis => "{ms}".to_string(),
+        Field::AmPm => "%p".to_string(),
+        Field::AmPmLower => "%P".to_string(),
+        Field::Offset => "%z".to_string(),
+        Field::OffsetColon => "%:z".to_string(),
+        Field::ZoneName => "%Z".to_string(),
+        Field::Epoch => "%s".to_string(),
+        Field::IsoWeek => "%V".to_string(),
+        Field::IsoYear => "%G".to_string(),
+    }
+}
+
+impl PatternLanguage {
+    /// Render a field, returning `None` if there's no equivalent, and
+    /// whether the rendering is exact. `before` is the pattern rendered so
+    /// far.
+    fn field(self, field: Field, before: &str) -> (Option<String>, bool) {
+        let (rendered, exact): (Option<&str>, bool) = match self {
+            PatternLanguage::Java => match field {
+                Field::Year => (Some("yyyy"), true),
+                Field::Year2 => (Some("yy"), true),
+                Field::Month(Pad::Zero) => (Some("MM"), true),
+                Field::Month(Pad::None) => (Some("M"), true),
+                Field::Month(Pad::Space) => (Some("ppM"), true),
+                Field::MonthAbbr => (Some("MMM"), true),
+                Field::MonthName => (Some("MMMM"), true),
+                Field::Day(Pad::Zero) => (Some("dd"), true),
+                Field::Day(Pad::None) => (Some("d"), true),
+                Field::Day(Pad::Space) => (Some("ppd"), true),
+                Field::DayOfYear(Pad::Zero) => (Some("DDD"), true),
+                Field::DayOfYear(Pad::None) => (Some("D"), true),
+                Field::DayOfYear(Pad::Space) => (Some("pppD"), true),
+                Field::WeekdayAbbr => (Some("EEE"), true),
+                Field::WeekdayName => (Some("EEEE"), true),
+                Field::Hour(Pad::Zero) => (Some("HH"), true),
+                Field::Hour(Pad::None) => (Some("H"), true),
+                Field::Hour(Pad::Space) => (Some("ppH"), true),
+                Field::Hour12(Pad::Zero) => (Some("hh"), true),
+                Field::Hour12(Pad::None) => (Some("h"), true),
+                Field::Hour12(Pad::Space) => (Some("pph"), true),
+                Field::Minute(Pad::None) => (Some("m"), true),
+                Field::Minute(_) => (Some("mm"), true),
+                Field::Second(Pad::None) => (Some("s"), true),
+                Field::Second(_) => (Some("ss"), true),
+                Field::Millis => (Some("SSS"), true),
+                Field::AmPm => (Some("a"), true),
+                Field::AmPmLower => (Some("a"), false),
+                Field::Offset => (Some("xx"), true),
+                Field::OffsetColon => (Some("xxx"), true),
+                Field::ZoneName => (Some("z"), true),
+                Field::WeekdayIso
+                | Field::WeekdayFromSunday
+                | Field::Epoch
+                | Field::IsoWeek
+                | Field::IsoYear => (None, false),
+            },
+            PatternLanguage::Moment => match field {
+                Field::Year => (Some("YYYY"), true),
+                Field::Year2 => (Some("YY"), true),
+                Field::Month(Pad::Zero) => (Some("MM"), true),
+                Field::Month(pad) => (Some("M"), pad == Pad::None),
+                Field::MonthAbbr => (Some("MMM"), true),
+                Field::MonthName => (Some("MMMM"), true),
+                Field::Day(Pad::Zero) => (Some("DD"), true),
+                Field::Day(pad) => (Some("D"), pad == Pad::None),
+                Field::DayOfYear(Pad::Zero) => (Some("DDDD"), true),
+                Field::DayOfYear(pad) => (Some("DDD"), pad == Pad::None),
+                Field::WeekdayAbbr => (Some("ddd"), true),
+                Field::WeekdayName => (Some("dddd"), true),
+                Field::WeekdayIso => (Some("E"), true),
+                Field::WeekdayFromSunday => (Some("d"), true),
+                Field::Hour(Pad::Zero) => (Some("HH"), true),
+                Field::Hour(pad) => (Some("H"), pad == Pad::None),
+                Field::Hour12(Pad::Zero) => (Some("hh"), true),
+                Field::Hour12(pad) => (Some("h"), pad == Pad::None),
+                Field::Minute(Pad::Zero) => (Some("mm"), true),
+                Field::Minute(pad) => (Some("m"), pad == Pad::None),
+                Field::Second(Pad::Zero) => (Some("ss"), true),
+                Field::Second(pad) => (Some("s"), pad == Pad::None),
+                Field::Millis => (Some("SSS"), true),
+                Field::AmPm => (Some("A"), true),
+                Field::AmPmLower => (Some("a"), true),
+                Field::Offset => (Some("ZZ"), true),
+                Field::OffsetColon => (Some("Z"), true),
+                Field::ZoneName => (Some("z"), true),
+                Field::Epoch => (Some("X"), true),
+                Field::IsoWeek => (Some("WW"), true),
+                Field::IsoYear => (Some("GGGG"), true),
+            },
+            PatternLanguage::Go => match field {
+                Field::Year => (Some("2006"), true),
+                Field::Year2 => (Some("06"), true),
+                Field::Month(Pad::Zero) => (Some("01"), true),
+                Field::Month(pad) => (Some("1"), pad == Pad::None),
+                Field::MonthAbbr => (Some("Jan"), true),
+                Field::MonthName => (Some("January"), true),
+                Field::Day(Pad::Zero) => (Some("02"), true),
+                Field::Day(Pad::None) => (Some("2"), true),
+                Field::Day(Pad::Space) => (Some("_2"), true),
+                Field::DayOfYear(Pad::Zero) => (Some("002"), true),
+                Field::DayOfYear(Pad::Space) => (Some("__2"), true),
+                Field::DayOfYear(Pad::None) => (Some("002"), false),
+                Field::WeekdayAbbr => (Some("Mon"), true),
+                Field::WeekdayName => (Some("Monday"), true),
+                Field::Hour(pad) => (Some("15"), pad == Pad::Zero),
+                Field::Hour12(Pad::Zero) => (Some("03"), true),
+                Field::Hour12(pad) => (Some("3"), pad == Pad::None),
+                Field::Minute(Pad::Zero) => (Some("04"), true),
+                Field::Minute(pad) => (Some("4"), pad == Pad::None),
+                Field::Second(Pad::Zero) => (Some("05"), true),
+                Field::Second(pad) => (Some("5"), pad == Pad::None),
+                // The separator is part of the fraction in Go layouts
+                Field::Millis if before.ends_with(['.', ',']) => (Some("000"), true),
+                Field::Millis => (None, false),
+                Field::AmPm => (Some("PM"), true),
+                Field::AmPmLower => (Some("pm"), true),
+                Field::Offset => (Some("-0700"), true),
+                Field::OffsetColon => (Some("-07:00"), true),
+                Field::ZoneName => (Some("MST"), true),
+                Field::WeekdayIso
+                | Field::WeekdayFromSunday
+                | Field::Epoch
+                | Field::IsoWeek
+                | Field::IsoYear => (None, false),
+            },
+            PatternLanguage::DotNet => match field {
+                Field::Year => (Some("yyyy"), true),
+                Field::Year2 => (Some("yy"), true),
+                Field::Month(Pad::Zero) => (Some("MM"), true),
+                Field::Month(pad) => (Some("%M"), pad == Pad::None),
+                Field::MonthAbbr => (Some("MMM"), true),
+                Field::MonthName => (Some("MMMM"), true),
+                Field::Day(Pad::Zero) => (Some("dd"), true),
+                Field::Day(pad) => (Some("%d"), pad == Pad::None),
+                Field::WeekdayAbbr => (Some("ddd"), true),
+                Field::WeekdayName => (Some("dddd"), true),
+                Field::Hour(Pad::Zero) => (Some("HH"), true),
+                Field::Hour(pad) => (Some("%H"), pad == Pad::None),
+                Field::Hour12(Pad::Zero) => (Some("hh"), true),
+                Field::Hour12(pad) => (Some("%h"), pad == Pad::None),
+                Field::Minute(Pad::Zero) => (Some("mm"), true),
+                Field::Minute(pad) => (Some("%m"), pad == Pad::None),
+                Field::Second(Pad::Zero) => (Some("ss"), true),
+                Field::Second(pad) => (Some("%s"), pad == Pad::None),
+                Field::Millis => (Some("fff"), true),
+                Field::AmPm => (Some("tt"), true),
+                Field::AmPmLower => (Some("tt"), false),
+                Field::Offset => (Some("zzz"), false),
+                Field::OffsetColon => (Some("zzz"), true),
+                Field::DayOfYear(_)
+                | Field::WeekdayIso
+                | Field::WeekdayFromSunday
+                | Field::ZoneName
+                | Field::Epoch
+                | Field::IsoWeek
+                | Field::IsoYear => (None, false),
+            },
+        };
+        // Letters repeated across fields would read as a single field:
+        // they are separated by an empty optional section or quote
+        let rendered = rendered.map(|rendered| {
+            let separator = match self {
+                PatternLanguage::Java | PatternLanguage::Moment => "[]",
+                PatternLanguage::Go => "",
+                PatternLanguage::DotNet => "''",
+            };
+            match (before.chars().last(), rendered.chars().next()) {
+                (Some(last), Some(first)) if last == first && first.is_ascii_alphabetic() => {
+                    format!("{}{}", separator, rendered)
+                }
+                _ => rendered.to_string(),
+            }
+        });
+        (rendered, exact)
+    }
+}
+
+fn parse_strftime(format: &str) -> Result<Vec<Piece>, Error> {
+    let tokens = strftime::parse(format)?;
+    let mut pieces = Vec::new();
+    let mut position = 0;
+    for token in tokens {
+        match token {
+            Token::Text(text) => {
+                pieces.push(Piece::literal(position, text));
+                position += text.len();
+            }
+            Token::Millis => {
+                pieces.push(Piece::field(position, "{ms}", Some(Field::Millis), true));
+                position += "{ms}".len();
+            }
+            Token::Spec(spec) => {
+                let construct = &format[spec.start..spec.end];
+                position = spec.end;
+                let pad = match spec.pad {
+                    Some(strftime::Pad::None) => Some(Pad::None),
+                    Some(strftime::Pad::Space) => Some(Pad::Space),
+                    Some(strftime::Pad::Zero) => Some(Pad::Zero),
+                    None => None,
+                };
+                let numeric = pad.unwrap_or(Pad::Zero);
+                let exact = spec.case.is_none() && spec.width.is_none() && spec.modifier.is_none();
+                let composite = |items: &[Item]| Piece {
+                    position: spec.start,
+                    construct: construct.to_string(),
+                    items: items.to_vec(),
+                    exact,
+                };
+                let literal = |s: &str| Item::Literal(s.to_string());
+                let piece = match spec.conv {
+                    'F' => composite(&[
+                        Item::Field(Field::Year),
+                        literal("-"),
+                        Item::Field(Field::Month(Pad::Zero)),
+                        literal("-"),
+                        Item::Field(Field::Day(Pad::Zero)),
+                    ]),
+                    'D' => composite(&[
+                        Item::Field(Field::Month(Pad::Zero)),
+                        literal("/"),
+                        Item::Field(Field::Day(Pad::Zero)),
+                        literal("/"),
+                        Item::Field(Field::Year2),
+                    ]),
+                    'T' => composite(&[
+                        Item::Field(Field::Hour(Pad::Zero)),
+                        literal(":"),
+                        Item::Field(Field::Minute(Pad::Zero)),
+                        literal(":"),
+                        Item::Field(Field::Second(Pad::Zero)),
+                    ]),
+                    'R' => composite(&[
+                        Item::Field(Field::Hour(Pad::Zero)),
+                        literal(":"),
+                        Item::Field(Field::Minute(Pad::Zero)),
+                    ]),
+                    'r' => composite(&[
+                        Item::Field(Field::Hour12(Pad::Zero)),
+                        literal(":"),
+                        Item::Field(Field::Minute(Pad::Zero)),
+                        literal(":"),
+                        Item::Field(Field::Second(Pad::Zero)),
+                        literal(" "),
+                        Item::Field(Field::AmPm),
+                    ]),
+                    'n' => composite(&[literal("\n")]),
+                    't' => composite(&[literal("\t")]),
+                    '%' => composite(&[literal("%")]),
+                    conv => {
+                        let field = match conv {
+                            'Y' => Some(Field::Year),
+                            'y' => Some(Field::Year2),
+                            'm' => Some(Field::Month(numeric)),
+                            'b' | 'h' => Some(Field::MonthAbbr),
+                            'B' => Some(Field::MonthName),
+                            'd' => Some(Field::Day(numeric)),
+                            'e' => Some(Field::Day(pad.unwrap_or(Pad::Space))),
+                            'j' => Some(Field::DayOfYear(numeric)),
+                            'a' => Some(Field::WeekdayAbbr),
+                            'A' => Some(Field::WeekdayName),
+                            'u' => Some(Field::WeekdayIso),
+                            'w' => Some(Field::WeekdayFromSunday),
+                            'H' => Some(Field::Hour(numeric)),
+                            'k' => Some(Field::Hour(pad.unwrap_or(Pad::Space))),
+                            'I' => Some(Field::Hour12(numeric)),
+                            'l' => Some(Field::Hour12(pad.unwrap_or(Pad::Space))),
+                            'M' => Some(Field::Minute(numeric)),
+                            'S' => Some(Field::Second(numeric)),
+                            'p' => Some(Field::AmPm),
+                            'P' => Some(Field::AmPmLower),
+                            'z' if spec.colons == 0 => Some(Field::Offset),
+                            'z' if spec.colons == 1 => Some(Field::OffsetColon),
+                            'Z' => Some(Field::ZoneName),
+                            's' => Some(Field::Epoch),
+                            'V' => Some(Field::IsoWeek),
+                            'G' => Some(Field::IsoYear),
+                            // Locale-dependent, or without equivalents elsewhere
+                            _ => None,
+                        };
+                        Piece::field(spec.start, construct, field, exact)
+                    }
+                };
+                pieces.push(piece);
+            }
+        }
+    }
+    Ok(pieces)
+}
+
+/// Length of the run of `c` at the start of `s`.
+fn run_length(s: &str, c: char) -> usize {
+    s.chars().take_while(|&d| d == c).count()
+}
+
+fn parse_java(pattern: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut i = 0;
+    while let Some(c) = pattern[i..].chars().next() {
+        if c == '\'' {
+            // Quoted text, with `''` for a quote inside quotes, and `''`
+            // alone for a quote
+            let mut j = i + 1;
+            let end = loop {
+                match pattern[j..].find('\'') {
+                    Some(end) if pattern[j + end + 1..].starts_with('\'') => j += end + 2,
+                    Some(end) => break j + end,
+                    None => break pattern.len(),
+                }
+            };
+            let text = match &pattern[i + 1..end] {
+                "" => "'".to_string(),
+                quoted => quoted.replace("''", "'"),
+            };
+            let j = pattern.len().min(end + 1);
+            pieces.push(Piece {
+                position: i,
+                construct: pattern[i..j].to_string(),
+                items: vec![Item::Literal(text)],
+                exact: true,
+            });
+            i = j;
+            continue;
+        }
+        if !c.is_ascii_alphabetic() {
+            let len = c.len_utf8();
+            let piece = match c {
+                // Empty optional sections, which separate letters
+                '[' if pattern[i + 1..].starts_with(']') => {
+                    pieces.push(Piece {
+                        position: i,
+                        construct: "[]".to_string(),
+                        items: Vec::new(),
+                        exact: true,
+                    });
+                    i += 2;
+                    continue;
+                }
+                // Optional sections
+                '[' | ']' => Piece::field(i, &pattern[i..i + len], None, false),
+                _ => Piece::literal(i, &pattern[i..i + len]),
+            };
+            pieces.push(piece);
+            i += len;
+            continue;
+        }
+        // Padding with spaces to the width of the `p` run
+        let pad_width = if c == 'p' {
+            run_length(&pattern[i..], 'p')
+        } else {
+            0
+        };
+        let letter_at = i + pad_width;
+        let letter = match pattern[letter_at..].chars().next() {
+            Some(letter) if letter.is_ascii_alphabetic() => letter,
+            _ => {
+                pieces.push(Piece::field(i, &pattern[i..letter_at], None, false));
+                i = letter_at;
+                continue;
+            }
+        };
+        let count = run_length(&pattern[letter_at..], letter);
+        let end = letter_at + count;
+        let construct = &pattern[i..end];
+        let (field, exact) = match (letter, count) {
+            ('y' | 'u', 2) => (Some(Field::Year2), true),
+            ('y' | 'u', _) => (Some(Field::Year), true),
+            ('M' | 'L', 1) => (Some(Field::Month(Pad::None)), true),
+            ('M' | 'L', 2) => (Some(Field::Month(Pad::Zero)), true),
+            ('M' | 'L', 3) => (Some(Field::MonthAbbr), true),
+            ('M' | 'L', 4) => (Some(Field::MonthName), true),
+            ('d', 1) => (Some(Field::Day(Pad::None)), true),
+            ('d', 2) => (Some(Field::Day(Pad::Zero)), true),
+            ('D', 1) => (Some(Field::DayOfYear(Pad::None)), true),
+            ('D', 3) => (Some(Field::DayOfYear(Pad::Zero)), true),
+            ('E', 1..=3) => (Some(Field::WeekdayAbbr), true),
+            ('E', 4) => (Some(Field::WeekdayName), true),
+            ('H', 1) => (Some(Field::Hour(Pad::None)), true),
+            ('H', 2) => (Some(Field::Hour(Pad::Zero)), true),
+            ('h', 1) => (Some(Field::Hour12(Pad::None)), true),
+            ('h', 2) => (Some(Field::Hour12(Pad::Zero)), true),
+            ('m', 1) => (Some(Field::Minute(Pad::None)), true),
+            ('m', 2) => (Some(Field::Minute(Pad::Zero)), true),
+            ('s', 1) => (Some(Field::Second(Pad::None)), true),
+            ('s', 2) => (Some(Field::Second(Pad::Zero)), true),
+            ('S', 3) => (Some(Field::Millis), true),
+            ('S', _) => (Some(Field::Millis), false),
+            ('a', 1) => (Some(Field::AmPm), true),
+            ('x', 2) | ('Z', 1..=3) => (Some(Field::Offset), true),
+            ('x', 3) => (Some(Field::OffsetColon), true),
+            // `X` writes `Z` for UTC
+            ('x' | 'X', 1 | 2 | 4) => (Some(Field::Offset), false),
+            ('X', 3) | ('x' | 'X', 5) | ('Z', 5) => (Some(Field::OffsetColon), false),
+            ('z', 1..=3) => (Some(Field::ZoneName), true),
+            _ => (None, false),
+        };
+        let piece = match (pad_width, field) {
+            (0, _) => Piece::field(i, construct, field, exact),
+            (2, Some(Field::Day(Pad::None))) => {
+                Piece::field(i, construct, Some(Field::Day(Pad::Space)), exact)
+            }
+            (2, Some(Field::Month(Pad::None))) => {
+                Piece::field(i, construct, Some(Field::Month(Pad::Space)), exact)
+            }
+            (2, Some(Field::Hour(Pad::None))) => {
+                Piece::field(i, construct, Some(Field::Hour(Pad::Space)), exact)
+            }
+            (2, Some(Field::Hour12(Pad::None))) => {
+                Piece::field(i, construct, Some(Field::Hour12(Pad::Space)), exact)
+            }
+            (3, Some(Field::DayOfYear(Pad::None))) => {
+                Piece::field(i, construct, Some(Field::DayOfYear(Pad::Space)), exact)
+            }
+            (_, field) => Piece::field(i, construct, field, false),
+        };
+        pieces.push(piece);
+        i = end;
+    }
+    pieces
+}
+
+/// moment.js tokens, longest first, and their meaning: `None` for tokens
+/// without a strftime equivalent.
+const MOMENT_TOKENS: &[(&str, Option<Field>, bool)] = &[
+    ("YYYYYY", Some(Field::Year), false),
+    ("YYYY", Some(Field::Year), true),
+    ("MMMM", Some(Field::MonthName), true),
+    ("DDDD", Some(Field::DayOfYear(Pad::Zero)), true),
+    ("dddd", Some(Field::WeekdayName), true),
+    ("GGGG", Some(Field::IsoYear), true),
+    ("gggg", None, false),
+    ("SSSS", Some(Field::Millis), false),
+    ("DDDo", None, false),
+    ("MMM", Some(Field::MonthAbbr), true),
+    ("DDD", Some(Field::DayOfYear(Pad::None)), true),
+    ("ddd", Some(Field::WeekdayAbbr), true),
+    ("SSS", Some(Field::Millis), true),
+    ("LTS", None, false),
+    ("LLLL", None, false),
+    ("LLL", None, false),
+    ("YY", Some(Field::Year2), true),
+    ("MM", Some(Field::Month(Pad::Zero)), true),
+    ("Mo", None, false),
+    ("DD", Some(Field::Day(Pad::Zero)), true),
+    ("Do", None, false),
+    ("dd", None, false),
+    ("do", None, false),
+    ("HH", Some(Field::Hour(Pad::Zero)), true),
+    ("hh", Some(Field::Hour12(Pad::Zero)), true),
+    ("kk", None, false),
+    ("mm", Some(Field::Minute(Pad::Zero)), true),
+    ("ss", Some(Field::Second(Pad::Zero)), true),
+    ("SS", Some(Field::Millis), false),
+    ("ZZ", Some(Field::Offset), true),
+    ("zz", Some(Field::ZoneName), true),
+    ("WW", Some(Field::IsoWeek), true),
+    ("ww", None, false),
+    ("GG", None, false),
+    ("gg", None, false),
+    ("Qo", None, false),
+    ("LT", None, false),
+    ("LL", None, false),
+    ("Y", Some(Field::Year), true),
+    ("M", Some(Field::Month(Pad::None)), true),
+    ("D", Some(Field::Day(Pad::None)), true),
+    ("d", Some(Field::WeekdayFromSunday), true),
+    ("E", Some(Field::WeekdayIso), true),
+    ("e", None, false),
+    ("H", Some(Field::Hour(Pad::None)), true),
+    ("h", Some(Field::Hour12(Pad::None)), true),
+    ("k", None, false),
+    ("m", Some(Field::Minute(Pad::None)), true),
+    ("s", Some(Field::Second(Pad::None)), true),
+    ("S", Some(Field::Millis), false),
+    ("A", Some(Field::AmPm), true),
+    ("a", Some(Field::AmPmLower), true),
+    ("Z", Some(Field::OffsetColon), true),
+    ("z", Some(Field::ZoneName), true),
+    ("X", Some(Field::Epoch), true),
+    ("x", None, false),
+    ("W", Some(Field::IsoWeek), false),
+    ("w", None, false),
+    ("Q", None, false),
+    ("L", None, false),
+    ("N", None, false),
+];
+
+fn parse_moment(pattern: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut i = 0;
+    while let Some(c) = pattern[i..].chars().next() {
+        let rest = &pattern[i..];
+        // Brackets escape the text up to the last `]` before the next `[`
+        if c == '[' {
+            let group = &rest[..rest[1..].find('[').map_or(rest.len(), |end| end + 1)];
+            if let Some(end) = group.rfind(']') {
+                pieces.push(Piece {
+                    position: i,
+                    construct: rest[..=end].to_string(),
+                    items: vec![Item::Literal(rest[1..end].to_string())],
+                    exact: true,
+                });
+                i += end + 1;
+                continue;
+            }
+        }
+        let escaped = c == '\\' && rest.len() > 1;
+        let token_start = if escaped { 1 } else { 0 };
+        let token = MOMENT_TOKENS
+            .iter()
+            .find(|(token, _, _)| rest[token_start..].starts_with(token));
+        match token {
+            // A backslash escapes the token or character after it
+            _ if escaped => {
+                let len = token.map_or_else(
+                    || rest[1..].chars().next().map_or(0, char::len_utf8),
+                    |(token, _, _)| token.len(),
+                );
+                pieces.push(Piece {
+                    position: i,
+                    construct: rest[..1 + len].to_string(),
+                    items: vec![Item::Literal(rest[1..1 + len].to_string())],
+                    exact: true,
+                });
+                i += 1 + len;
+            }
+            Some(&(token, field, exact)) => {
+                pieces.push(Piece::field(i, token, field, exact));
+                i += token.len();
+            }
+            None => {
+                pieces.push(Piece::literal(i, &rest[..c.len_utf8()]));
+                i += c.len_utf8();
+            }
+        }
+    }
+    pieces
+}
+
+/// Go layout elements, longest first, and their meaning.
+const GO_TOKENS: &[(&str, Option<Field>, bool)] = &[
+    ("January", Some(Field::MonthName), true),
+    ("Monday", Some(Field::WeekdayName), true),
+    ("Z07:00:00", Some(Field::OffsetColon), false),
+    ("-07:00:00", Some(Field::OffsetColon), false),
+    ("Z070000", Some(Field::Offset), false),
+    ("-070000", Some(Field::Offset), false),
+    ("Z07:00", Some(Field::OffsetColon), false),
+    ("-07:00", Some(Field::OffsetColon), true),
+    ("Z0700", Some(Field::Offset), false),
+    ("-0700", Some(Field::Offset), true),
+    ("2006", Some(Field::Year), true),
+    ("Z07", Some(Field::Offset), false),
+    ("-07", Some(Field::Offset), false),
+    ("Jan", Some(Field::MonthAbbr), true),
+    ("Mon", Some(Field::WeekdayAbbr), true),
+    ("MST", Some(Field::ZoneName), true),
+    ("002", Some(Field::DayOfYear(Pad::Zero)), true),
+    ("__2", Some(Field::DayOfYear(Pad::Space)), true),
+    ("_2", Some(Field::Day(Pad::Space)), true),
+    ("01", Some(Field::Month(Pad::Zero)), true),
+    ("02", Some(Field::Day(Pad::Zero)), true),
+    ("03", Some(Field::Hour12(Pad::Zero)), true),
+    ("04", Some(Field::Minute(Pad::Zero)), true),
+    ("05", Some(Field::Second(Pad::Zero)), true),
+    ("06", Some(Field::Year2), true),
+    ("15", Some(Field::Hour(Pad::Zero)), true),
+    ("PM", Some(Field::AmPm), true),
+    ("pm", Some(Field::AmPmLower), true),
+    ("1", Some(Field::Month(Pad::None)), true),
+    ("2", Some(Field::Day(Pad::None)), true),
+    ("3", Some(Field::Hour12(Pad::None)), true),
+    ("4", Some(Field::Minute(Pad::None)), true),
+    ("5", Some(Field::Second(Pad::None)), true),
+];
+
+fn parse_go(layout: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut i = 0;
+    while let Some(c) = layout[i..].chars().next() {
+        let rest = &layout[i..];
+        // Fractional seconds: a separator followed by zeros or nines
+        if matches!(c, '.' | ',') {
+            let digit = rest[1..].chars().next();
+            if let Some(digit @ ('0' | '9')) = digit {
+                let len = 1 + run_length(&rest[1..], digit);
+                if !rest[len..].starts_with(|c: char| c.is_ascii_digit()) {
+                    pieces.push(Piece {
+                        position: i,
+                        construct: rest[..len].to_string(),
+                        items: vec![Item::Literal(c.to_string()), Item::Field(Field::Millis)],
+                        exact: &rest[1..len] == "000",
+                    });
+                    i += len;
+                    continue;
+                }
+            }
+        }
+        match GO_TOKENS
+            .iter()
+            .find(|(token, _, _)| rest.starts_with(token))
+        {
+            Some(&(token, field, exact)) => {
+                pieces.push(Piece::field(i, token, field, exact));
+                i += token.len();
+            }
+            None => {
+                pieces.push(Piece::literal(i, &rest[..c.len_utf8()]));
+                i += c.len_utf8();
+            }
+        }
+    }
+    pieces
+}
+
+fn parse_dotnet(pattern: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut i = 0;
+    while let Some(c) = pattern[i..].chars().next() {
+        let rest = &pattern[i..];
+        match c {
+            '\'' | '"' => {
+                let end = rest[1..].find(c).map_or(rest.len(), |end| end + 2);
+                let text = rest[1..end].trim_end_matches(c);
+                pieces.push(Piece {
+                    position: i,
+                    construct: rest[..end].to_string(),
+                    items: vec![Item::Literal(text.to_string())],
+                    exact: true,
+                });
+                i += end;
+                continue;
+            }
+            '\\' => {
+                let len = 1 + rest[1..].chars().next().map_or(0, char::len_utf8);
+                pieces.push(Piece {
+                    position: i,
+                    construct: rest[..len].to_string(),
+                    items: vec![Item::Literal(rest[1..len].to_string())],
+                    exact: true,
+                });
+                i += len;
+                continue;
+            }
+            // A single custom specifier
+            '%' if rest.len() > 1 => {
+                pieces.push(Piece {
+                    position: i,
+                    construct: "%".to_string(),
+                    items: Vec::new(),
+                    exact: true,
+                });
+                i += 1;
+                let next = rest[1..].chars().next().unwrap_or('%');
+                let field = dotnet_field(next, 1);
+                pieces.push(match field {
+                    Some((field, exact)) => Piece::field(i, &rest[1..2], field, exact),
+                    None => Piece::literal(i, &rest[1..1 + next.len_utf8()]),
+                });
+                i += next.len_utf8();
+                continue;
+            }
+            _ => {}
+        }
+        let count = run_length(rest, c);
+        match dotnet_field(c, count) {
+            Some((field, exact)) => {
+                pieces.push(Piece::field(i, &rest[..count], field, exact));
+                i += count;
+            }
+            None => {
+                pieces.push(Piece::literal(i, &rest[..c.len_utf8()]));
+                i += c.len_utf8();
+            }
+        }
+    }
+    pieces
+}
+
+/// Meaning of a run of a .NET specifier letter, or `None` if the letter is
+/// literal text.
+fn dotnet_field(letter: char, count: usize) -> Option<(Option<Field>, bool)> {
+    let field = match (letter, count) {
+        ('y', 1) => (Some(Field::Year2), false),
+        ('y', 2) => (Some(Field::Year2), true),
+        ('y', 3 | 4) => (Some(Field::Year), true),
+        ('y', _) => (Some(Field::Year), false),
+        ('M', 1) => (Some(Field::Month(Pad::None)), true),
+        ('M', 2) => (Some(Field::Month(Pad::Zero)), true),
+        ('M', 3) => (Some(Field::MonthAbbr), true),
+        ('M', _) => (Some(Field::MonthName), true),
+        ('d', 1) => (Some(Field::Day(Pad::None)), true),
+        ('d', 2) => (Some(Field::Day(Pad::Zero)), true),
+        ('d', 3) => (Some(Field::WeekdayAbbr), true),
+        ('d', _) => (Some(Field::WeekdayName), true),
+        ('H', 1) => (Some(Field::Hour(Pad::None)), true),
+        ('H', _) => (Some(Field::Hour(Pad::Zero)), true),
+        ('h', 1) => (Some(Field::Hour12(Pad::None)), true),
+        ('h', _) => (Some(Field::Hour12(Pad::Zero)), true),
+        ('m', 1) => (Some(Field::Minute(Pad::None)), true),
+        ('m', _) => (Some(Field::Minute(Pad::Zero)), true),
+        ('s', 1) => (Some(Field::Second(Pad::None)), true),
+        ('s', _) => (Some(Field::Second(Pad::Zero)), true),
+        ('f', 3) => (Some(Field::Millis), true),
+        ('f' | 'F', _) => (Some(Field::Millis), false),
+        ('t', 1) => (None, false),
+        ('t', _) => (Some(Field::AmPm), true),
+        ('z', 1 | 2) => (Some(Field::Offset), false),
+        ('z', _) => (Some(Field::OffsetColon), true),
+        ('K', _) => (Some(Field::OffsetColon), false),
+        ('g', _) => (None, false),
+        _ => return None,
+    };
+    Some(field)
+}
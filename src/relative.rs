@@ -0,0 +1,247 @@
+//! Relative time formatting, such as "3 minutes ago" or "in 2 days".
+
+use crate::{now_ms, Error, TimeStampMs};
+
+/// Unit of a relative time.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
+pub enum RelativeUnit {
+    /// Seconds.
+    Second,
+    /// Minutes.
+    Minute,
+    /// Hours.
+    Hour,
+    /// Days.
+    Day,
+    /// Weeks (only used if enabled in the thresholds).
+    Week,
+    /// Months of 30.4375 days, a twelfth of a year.
+    Month,
+    /// Years of 365.25 days.
+    Year,
+}
+
+/// A time difference, rounded to a single unit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RelativeTime {
+    /// Number of units, negative for times in the past.
+    pub value: i64,
+    /// The unit.
+    pub unit: RelativeUnit,
+}
+
+/// Limits below which a unit is used rather than the next larger one.
+///
+/// The defaults show "44 seconds ago" but "1 minute ago" for 45 seconds,
+/// "21 hours" but "1 day" for 22 hours, and so on.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RelativeThresholds {
+    /// Number of seconds below which seconds are used.
+    pub seconds: u32,
+    /// Number of minutes below which minutes are used.
+    pub minutes: u32,
+    /// Number of hours below which hours are used.
+    pub hours: u32,
+    /// Number of days below which days are used.
+    pub days: u32,
+    /// Number of weeks below which weeks are used, or `None` to go straight
+    /// from days to months. Weeks are only used for differences of at least
+    /// `days` days, so `days` should be lowered to 7 along with this.
+    pub weeks: Option<u32>,
+    /// Number of months below which months are used, and years above.
+    pub months: u32,
+}
+
+impl Default for RelativeThresholds {
+    fn default() -> Self {
+        Self {
+            seconds: 45,
+            minutes: 45,
+            hours: 22,
+            days: 26,
+            weeks: None,
+            months: 11,
+        }
+    }
+}
+
+/// Phrases used to render relative times.
+///
+/// In every string, `{}` is replaced with the number of units, or with the
+/// rendered quantity in `past` and `future`.
+///
+/// ```rust
+/// use time_format::{RelativeFormatter, RelativeLocale, TimeStampMs};
+///
+/// let fr = RelativeLocale {
+///     now: "maintenant",
+///     past: "il y a {}",
+///     future: "dans {}",
+///     units: [
+///         ["{} seconde", "{} secondes"],
+///         ["{} minute", "{} minutes"],
+///         ["{} heure", "{} heures"],
+///         ["{} jour", "{} jours"],
+///         ["{} semaine", "{} semaines"],
+///         ["{} mois", "{} mois"],
+///         ["{} an", "{} ans"],
+///     ],
+/// };
+/// let formatter = RelativeFormatter { locale: fr, ..Default::default() };
+///
+/// let reference = TimeStampMs::new(1747751445, 0);
+/// let event = TimeStampMs::new(1747751445 - 3 * 3600, 0);
+/// assert_eq!(formatter.format(event, reference), "il y a 3 heures");
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RelativeLocale {
+    /// Phrase for a difference that rounds to zero seconds.
+    pub now: &'static str,
+    /// Phrase for times in the past.
+    pub past: &'static str,
+    /// Phrase for times in the future.
+    pub future: &'static str,
+    /// Singular and plural forms for each unit, in the order of [`RelativeUnit`].
+    pub units: [[&'static str; 2]; 7],
+}
+
+impl RelativeLocale {
+    /// English.
+    pub const EN: RelativeLocale = RelativeLocale {
+        now: "now",
+        past: "{} ago",
+        future: "in {}",
+        units: [
+            ["{} second", "{} seconds"],
+            ["{} minute", "{} minutes"],
+            ["{} hour", "{} hours"],
+            ["{} day", "{} days"],
+            ["{} week", "{} weeks"],
+            ["{} month", "{} months"],
+            ["{} year", "{} years"],
+        ],
+    };
+}
+
+impl Default for RelativeLocale {
+    fn default() -> Self {
+        RelativeLocale::EN
+    }
+}
+
+/// Relative time formatter, with configurable thresholds and phrases.
+///
+/// ```rust
+/// use time_format::{RelativeFormatter, RelativeThresholds, TimeStampMs};
+///
+/// let reference = TimeStampMs::new(1747751445, 0);
+/// let formatter = RelativeFormatter::default();
+///
+/// let past = TimeStampMs::new(reference.seconds - 180, 0);
+/// assert_eq!(formatter.format(past, reference), "3 minutes ago");
+///
+/// let future = TimeStampMs::new(reference.seconds + 2 * 86400, 0);
+/// assert_eq!(formatter.format(future, reference), "in 2 days");
+///
+/// // Use weeks between days and months
+/// let formatter = RelativeFormatter {
+///     thresholds: RelativeThresholds { days: 7, weeks: Some(4), ..Default::default() },
+///     ..Default::default()
+/// };
+/// let past = TimeStampMs::new(reference.seconds - 15 * 86400, 0);
+/// assert_eq!(formatter.format(past, reference), "2 weeks ago");
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct RelativeFormatter {
+    /// When to switch to the next larger unit.
+    pub thresholds: RelativeThresholds,
+    /// Phrases to render the result with.
+    pub locale: RelativeLocale,
+}
+
+/// Divide and round half away from zero.
+fn div_round(n: i64, d: i64) -> i64 {
+    n / d + i64::from(n % d >= (d + 1) / 2)
+}
+
+impl RelativeFormatter {
+    /// Compute the difference between `ts` and `reference`, rounded to the
+    /// most appropriate unit.
+    pub fn relative(&self, ts: TimeStampMs, reference: TimeStampMs) -> RelativeTime {
+        let diff_ms = ts
+            .total_milliseconds()
+            .saturating_sub(reference.total_milliseconds());
+        let sign = if diff_ms < 0 { -1 } else { 1 };
+        let t = &self.thresholds;
+
+        let seconds = div_round(diff_ms.saturating_abs(), 1000);
+        let minutes = div_round(seconds, 60);
+        let hours = div_round(seconds, 3600);
+        let days = div_round(seconds, 86400);
+        let weeks = div_round(seconds, 7 * 86400);
+        let months = div_round(seconds, 2_629_800); // 30.4375 days
+        let years = div_round(seconds, 31_557_600); // 365.25 days
+
+        let (value, unit) = if seconds < i64::from(t.seconds) {
+            (seconds, RelativeUnit::Second)
+        } else if minutes < i64::from(t.minutes) {
+            (minutes.max(1), RelativeUnit::Minute)
+        } else if hours < i64::from(t.hours) {
+            (hours.max(1), RelativeUnit::Hour)
+        } else if days < i64::from(t.days) {
+            (days.max(1), RelativeUnit::Day)
+        } else if t.weeks.map_or(false, |w| weeks < i64::from(w)) {
+            (weeks.max(1), RelativeUnit::Week)
+        } else if months < i64::from(t.months) {
+            (months.max(1), RelativeUnit::Month)
+        } else {
+            (years.max(1), RelativeUnit::Year)
+        };
+        RelativeTime {
+            value: sign * value,
+            unit,
+        }
+    }
+
+    /// Describe `ts` relative to `reference`, e.g. "3 minutes ago".
+    pub fn format(&self, ts: TimeStampMs, reference: TimeStampMs) -> String {
+        let RelativeTime { value, unit } = self.relative(ts, reference);
+        if value == 0 {
+            return self.locale.now.to_string();
+        }
+        let forms = self.locale.units[unit as usize];
+        let form = if value.abs() == 1 { forms[0] } else { forms[1] };
+        let quantity = form.replace("{}", &value.abs().to_string());
+        let phrase = if value < 0 {
+            self.locale.past
+        } else {
+            self.locale.future
+        };
+        phrase.replace("{}", &quantity)
+    }
+
+    /// Describe `ts` relative to the current time.
+    pub fn format_now(&self, ts: TimeStampMs) -> Result<String, Error> {
+        Ok(self.format(ts, now_ms()?))
+    }
+}
+
+/// Describe `ts` relative to `reference` in English, e.g. "3 minutes ago"
+/// or "in 2 days", using the default thresholds.
+///
+/// ```rust
+/// use time_format::TimeStampMs;
+///
+/// let reference = TimeStampMs::new(1747751445, 0);
+/// let event = TimeStampMs::new(1747751445 - 30, 0);
+/// assert_eq!(time_format::format_relative(event, reference), "30 seconds ago");
+/// ```
+pub fn format_relative(ts: TimeStampMs, reference: TimeStampMs) -> String {
+    RelativeFormatter::default().format(ts, reference)
+}
+
+/// Describe `ts` relative to the current time in English, using the
+/// default thresholds.
+pub fn format_relative_now(ts: TimeStampMs) -> Result<String, Error> {
+    RelativeFormatter::default().format_now(ts)
+}
@@ -0,0 +1,400 @@
+//! Rewriting of the timestamps found in log streams.
+//!
+//! A [`Rewriter`] scans lines of text for timestamps and replaces them
+//! with the same instants rendered in another format and time zone,
+//! leaving everything else untouched:
+//!
+//! ```rust
+//! use time_format::{rewrite::Rewriter, DateFormat, TimeZone};
+//!
+//! let rewriter = Rewriter::new(DateFormat::SQL).with_zone(TimeZone::FixedOffset(2 * 3600));
+//! let input = "1747751445 GET /index.html 200\n\
+//!              2025-05-20T14:30:46.250Z GET /style.css 304\n";
+//! let mut output = Vec::new();
+//! rewriter.rewrite(input.as_bytes(), &mut output).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(output).unwrap(),
+//!     "2025-05-20 16:30:45 GET /index.html 200\n\
+//!      2025-05-20 16:30:46.250 GET /style.css 304\n"
+//! );
+//! ```
+//!
+//! Three kinds of timestamps are recognized, each of which can be turned off:
+//!
+//! - bare epoch numbers in seconds, milliseconds, microseconds or
+//!   nanoseconds, whose unit is found by an [`EpochDetector`], and seconds
+//!   with a fractional part, if they fall in its window of plausible dates
+//!   (2000 to 2100 by default);
+//! - ISO 8601 dates with a time, such as `2025-05-20T14:30:45.123+02:00`
+//!   or `2025-05-20 14:30:45`;
+//! - syslog (RFC 3164) dates, such as `May 20 14:30:45`, whose year is
+//!   inferred relative to a reference time (the current time by default).
+//!
+//! Timestamps without a UTC offset are read in the input time zone, UTC
+//! by default. The input is processed line by line, and lines longer
+//! than [`Rewriter::with_max_line_length`] are processed in pieces, so
+//! memory use stays bounded whatever the input.
+
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    now_ms,
+    parse::{self, DateTimeFields},
+    DateFormat, EpochDetector, EpochUnit, FormatRef, Fraction, TimeStamp, TimeStampMs, TimeZone,
+};
+
+/// Maximum length of a timestamp in the input.
+const MAX_MATCH_LEN: usize = 40;
+
+/// Rewriter of the timestamps found in lines of text.
+///
+/// Timestamps are rendered as [`DateFormat::display_in_zone`] does, with
+/// milliseconds only if the original timestamp had a fractional part.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Rewriter<'a> {
+    format: FormatRef<'a>,
+    zone: TimeZone,
+    input_zone: TimeZone,
+    epochs: Option<EpochDetector>,
+    iso8601: bool,
+    syslog: bool,
+    reference: Option<TimeStamp>,
+    max_line_length: usize,
+}
+
+impl Default for Rewriter<'_> {
+    /// Rewrite to RFC 3339 in UTC.
+    fn default() -> Self {
+        Self::new(DateFormat::RFC3339)
+    }
+}
+
+impl<'a> Rewriter<'a> {
+    /// A rewriter to the given format in UTC, recognizing all the kinds of
+    /// timestamps.
+    ///
+    /// The format is a [`DateFormat`], or a reference to a
+    /// [`CustomFormat`](crate::CustomFormat).
+    pub fn new(format: impl Into<FormatRef<'a>>) -> Self {
+        Self {
+            format: format.into(),
+            zone: TimeZone::Utc,
+            input_zone: TimeZone::Utc,
+            epochs: Some(EpochDetector::default()),
+            iso8601: true,
+            syslog: true,
+            reference: None,
+            max_line_length: 64 * 1024,
+        }
+    }
+
+    /// Set the time zone of the rewritten timestamps.
+    pub fn with_zone(mut self, zone: TimeZone) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    /// Set the time zone of the input timestamps that have no UTC offset:
+    /// syslog dates and ISO 8601 dates without one.
+    pub fn with_input_zone(mut self, zone: TimeZone) -> Self {
+        self.input_zone = zone;
+        self
+    }
+
+    /// Set the detector of the unit of bare numbers, whose window of
+    /// plausible dates decides which numbers are timestamps, or `None` to
+    /// leave numbers alone. Numbers whose unit is ambiguous are left alone.
+    ///
+    /// ```rust
+    /// use time_format::{rewrite::Rewriter, DateFormat, EpochDetector};
+    ///
+    /// // Only the last day of May 2025
+    /// let rewriter = Rewriter::new(DateFormat::RFC3339)
+    ///     .with_epoch_detector(Some(EpochDetector::new(1748649600, 1748736000)));
+    /// assert_eq!(rewriter.rewrite_line("1747751445"), "1747751445");
+    /// assert_eq!(rewriter.rewrite_line("1748700000"), "2025-05-31T14:00:00+00:00");
+    /// ```
+    pub fn with_epoch_detector(mut self, detector: Option<EpochDetector>) -> Self {
+        self.epochs = detector;
+        self
+    }
+
+    /// Recognize ISO 8601 dates, or not.
+    pub fn with_iso8601(mut self, enabled: bool) -> Self {
+        self.iso8601 = enabled;
+        self
+    }
+
+    /// Recognize syslog dates, or not.
+    pub fn with_syslog(mut self, enabled: bool) -> Self {
+        self.syslog = enabled;
+        self
+    }
+
+    /// Set the reference time from which the year of syslog dates is
+    /// inferred: the year of the reference, or the previous one if the date
+    /// would otherwise be more than a month after the reference.
+    ///
+    /// ```rust
+    /// use time_format::{rewrite::Rewriter, DateFormat};
+    ///
+    /// // January 10, 2026
+    /// let rewriter = Rewriter::new(DateFormat::SQL).with_reference(1768003200);
+    /// assert_eq!(
+    ///     rewriter.rewrite_line("Dec 31 23:59:59 host cron[42]: done"),
+    ///     "2025-12-31 23:59:59 host cron[42]: done"
+    /// );
+    /// ```
+    pub fn with_reference(mut self, reference: TimeStamp) -> Self {
+        self.reference = Some(reference);
+        self
+    }
+
+    /// Set the length from which lines are processed in pieces, 64 KiB by
+    /// default. Timestamps across pieces are rewritten as a whole:
+    ///
+    /// ```rust
+    /// use time_format::{rewrite::Rewriter, DateFormat};
+    ///
+    /// let rewriter = Rewriter::new(DateFormat::SQL).with_max_line_length(40);
+    /// let input = format!("{}1747751445123 done\n", "x ".repeat(18));
+    /// let mut output = Vec::new();
+    /// rewriter.rewrite(input.as_bytes(), &mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().ends_with(" 2025-05-20 14:30:45.123 done\n"));
+    /// ```
+    pub fn with_max_line_length(mut self, len: usize) -> Self {
+        self.max_line_length = len.max(MAX_MATCH_LEN);
+        self
+    }
+
+    /// Rewrite the timestamps of a single line.
+    ///
+    /// ```rust
+    /// use time_format::{rewrite::Rewriter, DateFormat};
+    ///
+    /// let rewriter = Rewriter::new(DateFormat::HTTP);
+    /// assert_eq!(
+    ///     rewriter.rewrite_line("expires=1747751445123; path=/"),
+    ///     "expires=Tue, 20 May 2025 14:30:45 GMT; path=/"
+    /// );
+    /// ```
+    pub fn rewrite_line(&self, line: &str) -> String {
+        let mut out = Vec::with_capacity(line.len());
+        let reference = self.reference_time();
+        self.rewrite_bytes(line.as_bytes(), 0, line.len(), &mut out, reference);
+        String::from_utf8(out).expect("rewritten line is not UTF-8")
+    }
+
+    /// Copy `input` to `output`, rewriting the timestamps of every line.
+    /// Returns the number of timestamps rewritten.
+    ///
+    /// Lines don't need to be valid UTF-8.
+    pub fn rewrite<R: BufRead, W: Write>(&self, mut input: R, mut output: W) -> io::Result<u64> {
+        let reference = self.reference_time();
+        // The piece of the line being processed, after the end of the
+        // previous piece that is kept to look for timestamps across pieces
+        let mut line = Vec::new();
+        let mut start = 0;
+        let mut out = Vec::new();
+        let mut count = 0;
+        loop {
+            let read = read_line(&mut input, &mut line, self.max_line_length)?;
+            if read == 0 && line.len() == start {
+                break;
+            }
+            // A timestamp at the end of an unfinished line may continue in
+            // the next piece, so only positions followed by a whole window
+            // are processed
+            let complete = read == 0 || line.ends_with(b"\n");
+            let end = if complete {
+                line.len()
+            } else {
+                line.len().saturating_sub(MAX_MATCH_LEN).max(start)
+            };
+            out.clear();
+            let (rewritten, next) = self.rewrite_bytes(&line, start, end, &mut out, reference);
+            count += rewritten;
+            output.write_all(&out)?;
+            if complete {
+                line.clear();
+                start = 0;
+            } else {
+                // Keep the byte before the rest, to tell if it starts a word
+                let keep = next.saturating_sub(1);
+                line.drain(..keep);
+                start = next - keep;
+            }
+        }
+        output.flush()?;
+        Ok(count)
+    }
+
+    fn reference_time(&self) -> TimeStamp {
+        self.reference
+            .or_else(|| now_ms().ok().map(|ts_ms| ts_ms.seconds))
+            .unwrap_or(0)
+    }
+
+    /// Rewrite the timestamps starting in `line[start..end]` into `out`,
+    /// returning their number and the position after the rewritten text.
+    /// Timestamps may extend past `end`.
+    fn rewrite_bytes(
+        &self,
+        line: &[u8],
+        start: usize,
+        end: usize,
+        out: &mut Vec<u8>,
+        reference: TimeStamp,
+    ) -> (u64, usize) {
+        let mut count = 0;
+        let mut formatted = String::new();
+        let mut i = start;
+        while i < end {
+            let at_boundary = i == 0 || !line[i - 1].is_ascii_alphanumeric();
+            if at_boundary {
+                if let Some((len, ts_ms, with_ms)) = self.match_at(line, i, reference) {
+                    formatted.clear();
+                    let fraction = Some(Fraction::millis(ts_ms.milliseconds)).filter(|_| with_ms);
+                    let written =
+                        self.format
+                            .write_to(&mut formatted, ts_ms.seconds, fraction, self.zone);
+                    if written.is_ok() {
+                        out.extend_from_slice(formatted.as_bytes());
+                        count += 1;
+                        i += len;
+                        continue;
+                    }
+                }
+            }
+            out.push(line[i]);
+            i += 1;
+        }
+        (count, i)
+    }
+
+    /// Match a timestamp at the start of `line[i..]`, returning its length,
+    /// its value, and whether it had a fractional part.
+    fn match_at(
+        &self,
+        line: &[u8],
+        i: usize,
+        reference: TimeStamp,
+    ) -> Option<(usize, TimeStampMs, bool)> {
+        let window = &line[i..line.len().min(i + MAX_MATCH_LEN)];
+        let ascii_len = window
+            .iter()
+            .position(|b| !b.is_ascii())
+            .unwrap_or(window.len());
+        let s = std::str::from_utf8(&window[..ascii_len]).ok()?;
+        let first = *s.as_bytes().first()?;
+        let (len, ts_ms, with_ms) = if first.is_ascii_digit() {
+            self.match_iso8601(s)
+                .or_else(|| self.match_epoch(s, line.get(i.wrapping_sub(1)).copied()))?
+        } else if first.is_ascii_alphabetic() && self.syslog {
+            self.match_syslog(s, reference)?
+        } else {
+            return None;
+        };
+        // The timestamp must end at a word boundary too
+        match line.get(i + len) {
+            Some(b) if b.is_ascii_alphanumeric() => None,
+            _ => Some((len, ts_ms, with_ms)),
+        }
+    }
+
+    fn match_epoch(&self, s: &str, prev: Option<u8>) -> Option<(usize, TimeStampMs, bool)> {
+        let detector = self.epochs?;
+        // Parts of decimal numbers and of dotted identifiers aren't timestamps
+        if matches!(prev, Some(b'.') | Some(b'-') | Some(b'+')) {
+            return None;
+        }
+        let len = s.bytes().take_while(u8::is_ascii_digit).count();
+        let value: i64 = s[..len].parse().ok()?;
+        // Seconds with a fractional part
+        if let Some(fraction) = s[len..].strip_prefix('.') {
+            let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+            if digits > 0 {
+                let ms = fraction[..digits.min(3)]
+                    .bytes()
+                    .chain(std::iter::repeat(b'0'))
+                    .take(3)
+                    .fold(0u16, |ms, b| ms * 10 + u16::from(b - b'0'));
+                let ts_ms = TimeStampMs::new(value, ms);
+                if !detector.contains(ts_ms) {
+                    return None;
+                }
+                return Some((len + 1 + digits, ts_ms, true));
+            }
+        }
+        let (unit, ts_ms) = detector.detect(value).ok()?;
+        Some((len, ts_ms, unit != EpochUnit::Seconds))
+    }
+
+    fn match_iso8601(&self, s: &str) -> Option<(usize, TimeStampMs, bool)> {
+        if !self.iso8601 {
+            return None;
+        }
+        let (year, month, day, rest) = parse::parse_date(s).ok()?;
+        let rest = rest.strip_prefix(['T', 't', ' '])?;
+        let (hour, minute, second, nanosecond, rest) = parse::parse_time(rest).ok()?;
+        let with_ms = nanosecond != 0 || s.len() - rest.len() > 19;
+        // The longest valid offset at the end of the timestamp
+        let (offset, rest) = [6, 5, 3, 1]
+            .iter()
+            .filter_map(|&len| {
+                let candidate = rest.get(..len)?;
+                let offset = parse::parse_offset(candidate).ok()??;
+                Some((Some(offset), &rest[len..]))
+            })
+            .next()
+            .unwrap_or((None, rest));
+        let fields = DateTimeFields {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            nanosecond,
+            offset,
+        };
+        let ts_ms = match offset {
+            Some(_) => fields.to_timestamp().ok()?,
+            None => fields.to_timestamp_in(self.input_zone).ok()?,
+        };
+        Some((s.len() - rest.len(), ts_ms, with_ms))
+    }
+
+    fn match_syslog(&self, s: &str, reference: TimeStamp) -> Option<(usize, TimeStampMs, bool)> {
+        let (fields, rest) = parse::parse_rfc3164_prefix(s, reference).ok()?;
+        let ts_ms = fields.to_timestamp_in(self.input_zone).ok()?;
+        Some((s.len() - rest.len(), ts_ms, fields.nanosecond != 0))
+    }
+}
+
+/// Read a line including its terminator, or at most `max` bytes of it.
+/// Returns the number of bytes read, 0 at the end of the input.
+fn read_line<R: BufRead>(input: &mut R, line: &mut Vec<u8>, max: usize) -> io::Result<usize> {
+    let start = line.len();
+    while line.len() - start < max {
+        let available = match input.fill_buf() {
+            Ok(buf) => buf,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        if available.is_empty() {
+            break;
+        }
+        let limit = available.len().min(max - (line.len() - start));
+        let (used, done) = match available[..limit].iter().position(|&b| b == b'\n') {
+            Some(pos) => (pos + 1, true),
+            None => (limit, false),
+        };
+        line.extend_from_slice(&available[..used]);
+        input.consume(used);
+        if done {
+            break;
+        }
+    }
+    Ok(line.len() - start)
+}
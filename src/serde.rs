@@ -0,0 +1,313 @@
+//! Serde support, enabled with the `serde` feature.
+//!
+//! [`TimeStampMs`] is serialized as an RFC 3339 string with millisecond
+//! precision in human-readable formats such as JSON, and as an integer
+//! number of milliseconds otherwise. It can be deserialized from either.
+//!
+//! The modules in this one choose a specific representation for a field,
+//! with `#[serde(with = "...")]`:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use time_format::TimeStampMs;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "time_format::serde::rfc3339")]
+//!     created: TimeStampMs,
+//!     #[serde(with = "time_format::serde::seconds")]
+//!     updated: TimeStampMs,
+//!     #[serde(with = "time_format::serde::milliseconds")]
+//!     seen: TimeStampMs,
+//!     #[serde(with = "time_format::serde::http_date")]
+//!     expires: TimeStampMs,
+//! }
+//!
+//! let ts = TimeStampMs::new(1747751445, 123);
+//! let event = Event { created: ts, updated: ts, seen: ts, expires: ts };
+//! assert_eq!(
+//!     serde_json::to_string(&event).unwrap(),
+//!     r#"{"created":"2025-05-20T14:30:45.123Z","updated":1747751445,"seen":1747751445123,"expires":"Tue, 20 May 2025 14:30:45 GMT"}"#
+//! );
+//!
+//! let event: Event = serde_json::from_str(
+//!     r#"{"created":"2025-05-20T16:30:45.123+02:00","updated":1747751445,"seen":1747751445123,"expires":"Tue, 20 May 2025 14:30:45 GMT"}"#,
+//! )
+//! .unwrap();
+//! assert_eq!(event.created, ts);
+//! assert_eq!(event.updated, TimeStampMs::from_timestamp(1747751445));
+//!
+//! // Without an attribute, timestamps are RFC 3339 strings in JSON
+//! assert_eq!(serde_json::to_string(&ts).unwrap(), r#""2025-05-20T14:30:45.123Z""#);
+//! let parsed: TimeStampMs = serde_json::from_str("1747751445123").unwrap();
+//! assert_eq!(parsed, ts);
+//! ```
+//!
+//! [`Components`](crate::Components) are serialized like a derived type.
+//!
+//! [`DateFormat`] is serialized as its canonical name, and deserialized
+//! with [`DateFormat::from_name`], so that case, separators and aliases
+//! don't matter:
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use time_format::DateFormat;
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     format: DateFormat,
+//! }
+//!
+//! let config: Config = serde_json::from_str(r#"{"format":"rfc2822"}"#).unwrap();
+//! assert_eq!(config.format, DateFormat::RFC2822);
+//! let config: Config = serde_json::from_str(r#"{"format":"common-log"}"#).unwrap();
+//! assert_eq!(config.format, DateFormat::CommonLog);
+//! assert!(serde_json::from_str::<Config>(r#"{"format":"rfc9999"}"#).is_err());
+//!
+//! assert_eq!(serde_json::to_string(&DateFormat::ShortDate).unwrap(), r#""ShortDate""#);
+//! ```
+//!
+//! `DateFormat::Custom` is serialized as its format string, but can't be
+//! deserialized, since it doesn't own it. [`CustomFormat`] and
+//! [`FormatRef`] are serialized the same way, and a `CustomFormat` is
+//! validated when deserialized:
+//!
+//! ```rust
+//! use serde::Deserialize;
+//! use time_format::{CustomFormat, DateFormat, FormatRef};
+//!
+//! #[derive(Deserialize)]
+//! struct Config {
+//!     timestamp_format: CustomFormat,
+//! }
+//!
+//! let config: Config = serde_json::from_str(r#"{"timestamp_format":"%d.%m.%Y"}"#).unwrap();
+//! assert_eq!(config.timestamp_format.as_str(), "%d.%m.%Y");
+//! assert!(serde_json::from_str::<Config>(r#"{"timestamp_format":"%d.%m.%Y {"}"#).is_err());
+//!
+//! let format = FormatRef::from(&config.timestamp_format);
+//! assert_eq!(serde_json::to_string(&format).unwrap(), r#""%d.%m.%Y""#);
+//! assert!(serde_json::from_str::<DateFormat>(r#""%d.%m.%Y""#).is_err());
+//! ```
+
+use std::{convert::TryFrom, fmt};
+
+use serde::{
+    de::{self, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{format_iso8601_ms_utc, parse, CustomFormat, DateFormat, FormatRef, TimeStampMs};
+
+fn from_milliseconds(ms: i64) -> TimeStampMs {
+    TimeStampMs::new(ms.div_euclid(1000), ms.rem_euclid(1000) as u16)
+}
+
+impl Serialize for TimeStampMs {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            rfc3339::serialize(self, serializer)
+        } else {
+            milliseconds::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeStampMs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TimeStampMsVisitor;
+
+        impl Visitor<'_> for TimeStampMsVisitor {
+            type Value = TimeStampMs;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an RFC 3339 date or a number of milliseconds")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<TimeStampMs, E> {
+                parse::parse_rfc3339(v).map_err(E::custom)
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<TimeStampMs, E> {
+                Ok(from_milliseconds(v))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<TimeStampMs, E> {
+                let v = i64::try_from(v).map_err(|_| E::custom("timestamp out of range"))?;
+                Ok(from_milliseconds(v))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(TimeStampMsVisitor)
+        } else {
+            deserializer.deserialize_i64(TimeStampMsVisitor)
+        }
+    }
+}
+
+impl Serialize for CustomFormat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let format = String::deserialize(deserializer)?;
+        CustomFormat::new(format).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for DateFormat {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            DateFormat::Custom(format) => serializer.serialize_str(format),
+            format => serializer.serialize_str(format.name().unwrap_or_default()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DateFormat {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DateFormatVisitor;
+
+        impl Visitor<'_> for DateFormatVisitor {
+            type Value = DateFormat;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("the name of a date format")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                match DateFormat::from_name(v) {
+                    Some(format) => Ok(format),
+                    None if v.contains('%') => Err(E::custom(format_args!(
+                        "`{}` is a custom format, which must be deserialized as a CustomFormat",
+                        v
+                    ))),
+                    None => Err(E::custom(format_args!("unknown date format `{}`", v))),
+                }
+            }
+        }
+
+        deserializer.deserialize_str(DateFormatVisitor)
+    }
+}
+
+impl Serialize for FormatRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            FormatRef::Date(format) => format.serialize(serializer),
+            FormatRef::Custom(format) => format.serialize(serializer),
+        }
+    }
+}
+
+/// Deserialize a string with a parser from the [`parse`] module.
+fn deserialize_str<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    expecting: &'static str,
+    parser: fn(&str) -> Result<TimeStampMs, crate::Error>,
+) -> Result<TimeStampMs, D::Error> {
+    struct StrVisitor {
+        expecting: &'static str,
+        parser: fn(&str) -> Result<TimeStampMs, crate::Error>,
+    }
+
+    impl Visitor<'_> for StrVisitor {
+        type Value = TimeStampMs;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.expecting)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<TimeStampMs, E> {
+            (self.parser)(v).map_err(E::custom)
+        }
+    }
+
+    deserializer.deserialize_str(StrVisitor { expecting, parser })
+}
+
+/// (De)serialize a [`TimeStampMs`] as an RFC 3339 string in UTC, with
+/// millisecond precision: `"2025-05-20T14:30:45.123Z"`.
+///
+/// Any UTC offset is accepted when deserializing.
+pub mod rfc3339 {
+    use super::*;
+
+    /// Serialize a timestamp as an RFC 3339 string.
+    pub fn serialize<S: Serializer>(ts: &TimeStampMs, serializer: S) -> Result<S::Ok, S::Error> {
+        let s = format_iso8601_ms_utc(*ts).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&s)
+    }
+
+    /// Deserialize a timestamp from an RFC 3339 string.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TimeStampMs, D::Error> {
+        deserialize_str(deserializer, "an RFC 3339 date", parse::parse_rfc3339)
+    }
+}
+
+/// (De)serialize a [`TimeStampMs`] as an integer number of seconds since
+/// the UNIX epoch: `1747751445`.
+///
+/// The milliseconds are truncated when serializing.
+pub mod seconds {
+    use super::*;
+
+    /// Serialize a timestamp as a number of seconds.
+    pub fn serialize<S: Serializer>(ts: &TimeStampMs, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(ts.seconds)
+    }
+
+    /// Deserialize a timestamp from a number of seconds.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TimeStampMs, D::Error> {
+        i64::deserialize(deserializer).map(TimeStampMs::from_timestamp)
+    }
+}
+
+/// (De)serialize a [`TimeStampMs`] as an integer number of milliseconds
+/// since the UNIX epoch: `1747751445123`.
+pub mod milliseconds {
+    use super::*;
+
+    /// Serialize a timestamp as a number of milliseconds.
+    pub fn serialize<S: Serializer>(ts: &TimeStampMs, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(ts.total_milliseconds())
+    }
+
+    /// Deserialize a timestamp from a number of milliseconds.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TimeStampMs, D::Error> {
+        i64::deserialize(deserializer).map(from_milliseconds)
+    }
+}
+
+/// (De)serialize a [`TimeStampMs`] as an HTTP date (RFC 7231):
+/// `"Tue, 20 May 2025 14:30:45 GMT"`.
+///
+/// The milliseconds are truncated when serializing.
+pub mod http_date {
+    use super::*;
+    use crate::{format_common_utc, DateFormat};
+
+    /// Serialize a timestamp as an HTTP date.
+    pub fn serialize<S: Serializer>(ts: &TimeStampMs, serializer: S) -> Result<S::Ok, S::Error> {
+        let s =
+            format_common_utc(ts.seconds, DateFormat::HTTP).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&s)
+    }
+
+    /// Deserialize a timestamp from an HTTP date.
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<TimeStampMs, D::Error> {
+        deserialize_str(deserializer, "an HTTP date", parse::parse_http_date)
+    }
+}
@@ -0,0 +1,470 @@
+//! Parsing of strftime format strings, including the `{ms}` placeholder,
+//! and rendering of the GNU extensions (padding and case flags, field
+//! widths, `%:z`) that the system strftime may not support.
+
+use std::fmt;
+
+use crate::{tm, FormatErrorReason, FormatStringError};
+
+/// The placeholder for the milliseconds component.
+const PLACEHOLDER_MS: &str = "{ms}";
+
+/// Largest accepted field width, e.g. `%255Y`.
+const MAX_WIDTH: usize = 255;
+
+/// Padding flag of a directive.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) enum Pad {
+    /// `-`: do not pad numeric fields
+    None,
+    /// `_`: pad numeric fields with spaces
+    Space,
+    /// `0`: pad numeric fields with zeros
+    Zero,
+}
+
+/// Case flag of a directive.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) enum Case {
+    /// `^`: convert to uppercase
+    Upper,
+    /// `#`: swap case (lowercase for `%Z` and `%p`, uppercase for names)
+    Swap,
+}
+
+/// A single `%` directive.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) struct Spec {
+    /// Byte range of the whole directive, including the `%`.
+    pub start: usize,
+    pub end: usize,
+    pub pad: Option<Pad>,
+    pub case: Option<Case>,
+    pub width: Option<usize>,
+    /// Number of colons, only allowed before `z`.
+    pub colons: u8,
+    /// The `E` (alternative era) or `O` (alternative numerals) modifier.
+    pub modifier: Option<char>,
+    /// The conversion specifier.
+    pub conv: char,
+}
+
+impl Spec {
+    /// Whether the directive can be passed to the system strftime as is.
+    pub fn is_plain(&self) -> bool {
+        self.pad.is_none() && self.case.is_none() && self.width.is_none() && self.colons == 0
+    }
+}
+
+/// A parsed piece of a format string.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Token<'a> {
+    /// Literal text, copied to the output.
+    Text(&'a str),
+    /// A `%` directive.
+    Spec(Spec),
+    /// The `{ms}` placeholder.
+    Millis,
+}
+
+/// A token located in its format string, so that the tokens of an owned
+/// format string can be stored along with it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub(crate) enum TokenSpan {
+    /// Byte range of literal text.
+    Text(usize, usize),
+    /// A `%` directive.
+    Spec(Spec),
+    /// The `{ms}` placeholder.
+    Millis,
+}
+
+impl TokenSpan {
+    /// The token in the format string it was located in.
+    pub fn token(self, format: &str) -> Token<'_> {
+        match self {
+            Self::Text(start, end) => Token::Text(&format[start..end]),
+            Self::Spec(spec) => Token::Spec(spec),
+            Self::Millis => Token::Millis,
+        }
+    }
+}
+
+/// Conversion specifiers accepted after `%`.
+const CONVERSIONS: &str = "aAbBcCdDeFgGhHIjklmMnpPrRsStTuUVwWxXyYzZ%+";
+
+/// Conversion specifiers accepted after the `%E` modifier.
+const E_CONVERSIONS: &str = "cCxXyY";
+
+/// Conversion specifiers accepted after the `%O` modifier.
+const O_CONVERSIONS: &str = "deHImMSuUVwWy";
+
+fn is_conversion(c: char) -> bool {
+    CONVERSIONS.contains(c)
+}
+
+/// Iterator over the tokens of a format string.
+///
+/// `{{` and `}}` are escapes for literal braces, and `{ms}` is the only
+/// placeholder. The iterator ends after the first error.
+pub(crate) struct Tokens<'a> {
+    format: &'a str,
+    pos: usize,
+}
+
+/// Split a format string into literal text, `%` directives and placeholders,
+/// without allocating.
+pub(crate) fn tokens(format: &str) -> Tokens<'_> {
+    Tokens { format, pos: 0 }
+}
+
+/// Split a format string into a vector of tokens, to format it repeatedly.
+pub(crate) fn parse(format: &str) -> Result<Vec<Token<'_>>, FormatStringError> {
+    tokens(format).collect()
+}
+
+/// Split a format string into tokens located by their byte ranges.
+pub(crate) fn spans(format: &str) -> Result<Vec<TokenSpan>, FormatStringError> {
+    let mut tokens = tokens(format);
+    let mut spans = Vec::new();
+    loop {
+        let start = tokens.pos;
+        let span = match tokens.next() {
+            None => return Ok(spans),
+            Some(token) => match token? {
+                Token::Text(text) => TokenSpan::Text(start, start + text.len()),
+                Token::Spec(spec) => TokenSpan::Spec(spec),
+                Token::Millis => TokenSpan::Millis,
+            },
+        };
+        spans.push(span);
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token<'a>, FormatStringError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let format = self.format;
+        let bytes = format.as_bytes();
+        let start = self.pos;
+        if start >= bytes.len() {
+            return None;
+        }
+        let text_end = bytes[start..]
+            .iter()
+            .position(|b| matches!(b, b'%' | b'{' | b'}'))
+            .map_or(bytes.len(), |offset| start + offset);
+        if start < text_end {
+            self.pos = text_end;
+            return Some(Ok(Token::Text(&format[start..text_end])));
+        }
+        let i = start;
+        let token = match bytes[i] {
+            b'%' => parse_spec(format, i).map(|spec| (Token::Spec(spec), spec.end)),
+            b'{' if bytes.get(i + 1) == Some(&b'{') => Ok((Token::Text(&format[i..i + 1]), i + 2)),
+            b'}' if bytes.get(i + 1) == Some(&b'}') => Ok((Token::Text(&format[i..i + 1]), i + 2)),
+            b'{' => {
+                if format[i..].starts_with(PLACEHOLDER_MS) {
+                    Ok((Token::Millis, i + PLACEHOLDER_MS.len()))
+                } else if format[i..].contains('}') {
+                    Err(FormatStringError::new(
+                        i,
+                        Some('{'),
+                        FormatErrorReason::UnknownPlaceholder,
+                    ))
+                } else {
+                    Err(FormatStringError::new(
+                        i,
+                        Some('{'),
+                        FormatErrorReason::UnmatchedOpeningBrace,
+                    ))
+                }
+            }
+            _ => Err(FormatStringError::new(
+                i,
+                Some('}'),
+                FormatErrorReason::UnmatchedClosingBrace,
+            )),
+        };
+        Some(match token {
+            Ok((token, end)) => {
+                self.pos = end;
+                Ok(token)
+            }
+            Err(err) => {
+                self.pos = bytes.len();
+                Err(err)
+            }
+        })
+    }
+}
+
+fn parse_spec(format: &str, start: usize) -> Result<Spec, FormatStringError> {
+    // Flags, widths, colons and modifiers are ASCII, and scanned as bytes
+    let bytes = format.as_bytes();
+    let mut pos = start + 1;
+    let mut spec = Spec {
+        start,
+        end: start,
+        pad: None,
+        case: None,
+        width: None,
+        colons: 0,
+        modifier: None,
+        conv: '%',
+    };
+
+    // Flags
+    while let Some(&b) = bytes.get(pos) {
+        match b {
+            b'-' => spec.pad = Some(Pad::None),
+            b'_' => spec.pad = Some(Pad::Space),
+            b'0' => spec.pad = Some(Pad::Zero),
+            b'^' => spec.case = Some(Case::Upper),
+            b'#' => spec.case = Some(Case::Swap),
+            _ => break,
+        }
+        pos += 1;
+    }
+
+    // Field width
+    while let Some(&b) = bytes.get(pos).filter(|b| b.is_ascii_digit()) {
+        let w = spec.width.unwrap_or(0) * 10 + usize::from(b - b'0');
+        if w > MAX_WIDTH {
+            return Err(FormatStringError::new(
+                start,
+                None,
+                FormatErrorReason::WidthTooLarge,
+            ));
+        }
+        spec.width = Some(w);
+        pos += 1;
+    }
+
+    // Colons, for `%:z` and `%::z`
+    while bytes.get(pos) == Some(&b':') {
+        spec.colons += 1;
+        pos += 1;
+    }
+
+    // `E` and `O` modifiers
+    if let Some(&b @ b'E') | Some(&b @ b'O') = bytes.get(pos) {
+        spec.modifier = Some(char::from(b));
+        pos += 1;
+    }
+
+    let conv = match format[pos..].chars().next() {
+        Some(conv) => conv,
+        None => {
+            return Err(FormatStringError::new(
+                start,
+                None,
+                FormatErrorReason::TrailingPercent,
+            ))
+        }
+    };
+    let allowed = match spec.modifier {
+        Some('E') => Some(E_CONVERSIONS),
+        Some(_) => Some(O_CONVERSIONS),
+        None => None,
+    };
+    if let Some(allowed) = allowed {
+        if !allowed.contains(conv) {
+            return Err(FormatStringError::new(
+                start,
+                Some(conv),
+                FormatErrorReason::InvalidModifier,
+            ));
+        }
+    } else if !is_conversion(conv) {
+        return Err(FormatStringError::new(
+            start,
+            Some(conv),
+            FormatErrorReason::UnknownSpecifier,
+        ));
+    }
+    if spec.colons > 0 && (conv != 'z' || spec.colons > 2) {
+        return Err(FormatStringError::new(
+            start,
+            Some(conv),
+            FormatErrorReason::InvalidColonModifier,
+        ));
+    }
+    spec.conv = conv;
+    spec.end = pos + conv.len_utf8();
+    Ok(spec)
+}
+
+/// Whether a conversion produces a number, and its default padding character.
+fn numeric_padding(conv: char) -> Option<char> {
+    match conv {
+        'e' | 'k' | 'l' => Some(' '),
+        'C' | 'd' | 'g' | 'G' | 'H' | 'I' | 'j' | 'm' | 'M' | 's' | 'S' | 'u' | 'U' | 'V' | 'w'
+        | 'W' | 'y' | 'Y' => Some('0'),
+        _ => None,
+    }
+}
+
+/// Render `%:z` (`+hh:mm`) and `%::z` (`+hh:mm:ss`) from the UTC offset.
+pub(crate) fn write_offset<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    tm: &tm,
+    colons: u8,
+) -> fmt::Result {
+    // `c_long` is only 32 bits wide on some platforms
+    #[allow(clippy::useless_conversion)]
+    let offset = i64::from(tm.tm_gmtoff);
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.abs();
+    let (hours, minutes, seconds) = (offset / 3600, offset / 60 % 60, offset % 60);
+    if colons >= 2 {
+        write!(out, "{}{:02}:{:02}:{:02}", sign, hours, minutes, seconds)
+    } else {
+        write!(out, "{}{:02}:{:02}", sign, hours, minutes)
+    }
+}
+
+/// Write the directive without its flags, width and colons, as passed to
+/// the system strftime, into a small buffer.
+pub(crate) fn plain_directive<'b>(spec: &Spec, buf: &'b mut [u8; 8]) -> &'b str {
+    let mut len = 0;
+    for c in std::iter::once('%')
+        .chain(spec.modifier)
+        .chain(std::iter::once(spec.conv))
+    {
+        len += c.encode_utf8(&mut buf[len..]).len();
+    }
+    // Only ASCII characters were written
+    std::str::from_utf8(&buf[..len]).unwrap_or_default()
+}
+
+/// Write the unmodified rendering `base` of a directive, with the flags and
+/// width of the directive applied.
+pub(crate) fn write_flagged<W: fmt::Write + ?Sized>(
+    out: &mut W,
+    spec: &Spec,
+    base: &str,
+) -> fmt::Result {
+    // Era-based representations are names rather than numbers
+    let padding = match spec.modifier {
+        Some('E') => None,
+        _ => numeric_padding(spec.conv),
+    };
+    let (fill, fill_len, sign, body) = match padding {
+        Some(default_pad) => {
+            let (sign, digits) = match base.chars().next() {
+                Some(c @ '-') | Some(c @ '+') => (Some(c), &base[1..]),
+                _ => (None, base),
+            };
+            let trimmed = digits.trim_start_matches(['0', ' ']);
+            let digits = if trimmed.is_empty() && !digits.is_empty() {
+                "0"
+            } else {
+                trimmed
+            };
+            let pad = match spec.pad {
+                Some(Pad::None) => None,
+                Some(Pad::Space) => Some(' '),
+                Some(Pad::Zero) => Some('0'),
+                None => Some(default_pad),
+            };
+            let width = spec.width.unwrap_or(base.len());
+            let len = sign.map_or(0, |_| 1) + digits.len();
+            (pad, width.saturating_sub(len), sign, digits)
+        }
+        None => {
+            let pad = match spec.pad {
+                Some(Pad::None) => None,
+                Some(Pad::Zero) => Some('0'),
+                _ => Some(' '),
+            };
+            let width = spec.width.unwrap_or(0);
+            (pad, width.saturating_sub(base.chars().count()), None, base)
+        }
+    };
+    let write_fill = |out: &mut W, fill: char| (0..fill_len).try_for_each(|_| out.write_char(fill));
+    // Zeros go between the sign and the digits, spaces before the sign
+    match fill {
+        Some('0') => {
+            if let Some(sign) = sign {
+                out.write_char(sign)?;
+            }
+            write_fill(out, '0')?;
+        }
+        Some(fill) => {
+            write_fill(out, fill)?;
+            if let Some(sign) = sign {
+                out.write_char(sign)?;
+            }
+        }
+        None => {
+            if let Some(sign) = sign {
+                out.write_char(sign)?;
+            }
+        }
+    }
+    let upper = match (spec.case, spec.conv) {
+        (Some(Case::Upper), _) => Some(true),
+        (Some(Case::Swap), 'Z') | (Some(Case::Swap), 'p') => Some(false),
+        (Some(Case::Swap), 'a' | 'A' | 'b' | 'B' | 'h') => Some(true),
+        _ => None,
+    };
+    match upper {
+        Some(true) => body
+            .chars()
+            .flat_map(char::to_uppercase)
+            .try_for_each(|c| out.write_char(c)),
+        Some(false) => body
+            .chars()
+            .flat_map(char::to_lowercase)
+            .try_for_each(|c| out.write_char(c)),
+        None => out.write_str(body),
+    }
+}
+
+/// A string in a fixed-size buffer on the stack.
+///
+/// Writes that don't fit are rejected as a whole, leaving the buffer as is.
+pub(crate) struct StackBuf {
+    buf: [u8; StackBuf::CAPACITY],
+    len: usize,
+}
+
+impl StackBuf {
+    /// Capacity in bytes, short enough for the buffer and its NUL
+    /// terminator to fit in the stack buffers of the strftime wrappers.
+    pub const CAPACITY: usize = 255;
+
+    pub fn new() -> Self {
+        Self {
+            buf: [0; Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        // Only whole strings are copied into the buffer
+        std::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl fmt::Write for StackBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > Self::CAPACITY {
+            return Err(fmt::Error);
+        }
+        self.buf[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+//! Event timestamps for `tracing-subscriber`, enabled with the
+//! `tracing-subscriber` feature.
+//!
+//! [`Timer`] implements `tracing_subscriber::fmt::time::FormatTime`, and
+//! writes directly into the subscriber's buffer:
+//!
+//! ```rust
+//! use time_format::{tracing::Timer, CustomFormat, DateFormat, Precision, TimeStampUs, TimeZone};
+//!
+//! let timer = Timer::new(DateFormat::RFC3339)
+//!     .unwrap()
+//!     .with_zone(TimeZone::FixedOffset(2 * 3600))
+//!     .with_precision(Precision::Microseconds);
+//! # let _ =
+//! tracing_subscriber::fmt().with_timer(timer);
+//!
+//! let mut buf = String::new();
+//! let timer = Timer::strftime("%d/%b/%Y:%H:%M:%S.{ms} %z")
+//!     .unwrap()
+//!     .with_zone(TimeZone::FixedOffset(-5 * 3600));
+//! timer
+//!     .write(&mut buf, time_format::TimeStampMs::new(1747751445, 123))
+//!     .unwrap();
+//! assert_eq!(buf, "20/May/2025:09:30:45.123 -0500");
+//!
+//! // A format loaded at run time, with microseconds
+//! let format: CustomFormat = String::from("%H:%M:%S.{ms}").parse().unwrap();
+//! let timer = Timer::custom(format).with_precision(Precision::Microseconds);
+//! buf.clear();
+//! timer
+//!     .write(&mut buf, TimeStampUs::new(1747751445, 123456))
+//!     .unwrap();
+//! assert_eq!(buf, "14:30:45.123456");
+//! ```
+
+use std::{convert::TryFrom, fmt, time::SystemTime};
+
+use tracing_subscriber::fmt::{format::Writer, time::FormatTime};
+
+use crate::{
+    cache::CachedFormat, check_format, CustomFormat, DateFormat, Error, FormatRef, Precision,
+    TimeStampUs, TimeZone,
+};
+
+/// A timer rendering event timestamps with a [`DateFormat`] or a
+/// [`CustomFormat`].
+///
+/// The format is validated when the timer is created, and a custom format
+/// is kept split into tokens. The `{ms}` placeholder of custom formats is
+/// replaced with the fractional seconds at the precision of the timer:
+/// three digits for milliseconds, six for microseconds, and zeros for
+/// seconds.
+///
+/// As with [`LogTimestamp`](crate::log::LogTimestamp), the rendering of
+/// the date and time is cached per thread and reused while the second
+/// doesn't change.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    format: TimerFormat,
+    zone: TimeZone,
+    precision: Precision,
+}
+
+#[derive(Debug, Clone)]
+enum TimerFormat {
+    Date(DateFormat),
+    Custom(CustomFormat),
+}
+
+impl Default for Timer {
+    /// RFC 3339 timestamps in UTC, with millisecond precision.
+    fn default() -> Self {
+        Self::with_format(TimerFormat::Date(DateFormat::RFC3339))
+    }
+}
+
+impl Timer {
+    /// A timer using a common date format, in UTC with millisecond precision.
+    ///
+    /// Fractional seconds are only rendered for the formats that can
+    /// include them (see [`format_common_ms_utc`](crate::format_common_ms_utc)).
+    ///
+    /// Fails if the format is [`DateFormat::Custom`] with an invalid format
+    /// string.
+    pub fn new(format: DateFormat) -> Result<Self, Error> {
+        check_format(format.pattern_for(Precision::Milliseconds))?;
+        Ok(Self::with_format(TimerFormat::Date(format)))
+    }
+
+    /// A timer using a validated custom format, in UTC with millisecond
+    /// precision.
+    pub fn custom(format: CustomFormat) -> Self {
+        Self::with_format(TimerFormat::Custom(format))
+    }
+
+    /// A timer using a strftime format string, in UTC with millisecond
+    /// precision.
+    pub fn strftime(format: impl Into<String>) -> Result<Self, Error> {
+        CustomFormat::new(format).map(Self::custom)
+    }
+
+    fn with_format(format: TimerFormat) -> Self {
+        Self {
+            format,
+            zone: TimeZone::Utc,
+            precision: Precision::Milliseconds,
+        }
+    }
+
+    /// Set the time zone. HTTP dates ignore it and always use UTC.
+    pub fn with_zone(mut self, zone: TimeZone) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    /// Set the precision of the fractional seconds.
+    pub fn with_precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Write a timestamp.
+    ///
+    /// The timestamp is a [`TimeStampUs`], or a
+    /// [`TimeStampMs`](crate::TimeStampMs), which has no digits past the
+    /// milliseconds.
+    pub fn write<W: fmt::Write + ?Sized>(
+        &self,
+        w: &mut W,
+        ts: impl Into<TimeStampUs>,
+    ) -> fmt::Result {
+        let ts_us = ts.into();
+        let format = CachedFormat {
+            format: match &self.format {
+                TimerFormat::Date(format) => FormatRef::Date(*format),
+                TimerFormat::Custom(format) => format.format_ref(),
+            },
+            zone: self.zone,
+            precision: self.precision,
+        };
+        format
+            .write(w, ts_us.seconds, self.precision.fraction(ts_us))
+            .map_err(|_| fmt::Error)
+    }
+}
+
+impl FormatTime for Timer {
+    fn format_time(&self, w: &mut Writer<'_>) -> fmt::Result {
+        let ts_us = TimeStampUs::try_from(SystemTime::now()).map_err(|_| fmt::Error)?;
+        self.write(w, ts_us)
+    }
+}